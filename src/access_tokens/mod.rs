@@ -0,0 +1,357 @@
+extern crate serde_json;
+
+use self::super::{Gitlab, Credentials, Error, ErrorKind, Result, ProjectOrGroup};
+use members::AccessLevel;
+use secret::Secret;
+
+use url::form_urlencoded;
+
+use std::collections::HashMap;
+
+/// A client for the [Personal access tokens API](https://docs.gitlab.com/ee/api/personal_access_tokens.html)
+pub struct PersonalAccessTokens<'a> {
+    gitlab: &'a Gitlab,
+}
+
+/// A client for [project](https://docs.gitlab.com/ee/api/project_access_tokens.html)
+/// and [group](https://docs.gitlab.com/ee/api/group_access_tokens.html) access tokens.
+pub struct ResourceAccessTokens<'a> {
+    gitlab: &'a Gitlab,
+    source: ProjectOrGroup,
+}
+
+/// A personal, project or group access token. `token` is only present in
+/// the response to creating or rotating a token.
+#[derive(Debug, Deserialize)]
+pub struct AccessToken {
+    pub id: u64,
+    pub name: String,
+    pub user_id: u64,
+    pub active: bool,
+    pub revoked: bool,
+    #[serde(default)]
+    pub scopes: Vec<String>,
+    pub created_at: String,
+    pub expires_at: Option<String>,
+    pub last_used_at: Option<String>,
+    pub access_level: Option<AccessLevel>,
+    pub token: Option<Secret>,
+}
+
+impl AccessToken {
+    /// Credentials for authenticating a new `Gitlab` client with this token.
+    pub fn credentials(&self) -> Option<Credentials> {
+        self.token.as_ref().map(|token| Credentials::AccessToken(token.expose().to_owned()))
+    }
+}
+
+#[derive(Default)]
+pub struct PersonalAccessTokensOptions {
+    pub params: HashMap<&'static str, String>,
+}
+
+impl PersonalAccessTokensOptions {
+    pub fn builder() -> PersonalAccessTokensOptionsBuilder {
+        PersonalAccessTokensOptionsBuilder::new()
+    }
+
+    pub fn to_query_string(&self) -> Option<String> {
+        if self.params.is_empty() {
+            None
+        } else {
+            let encoded_qs: String = form_urlencoded::Serializer::new(String::new())
+                .extend_pairs(&self.params)
+                .finish();
+            Some(encoded_qs)
+        }
+    }
+}
+
+pub struct PersonalAccessTokensOptionsBuilder(PersonalAccessTokensOptions);
+
+impl PersonalAccessTokensOptionsBuilder {
+    pub fn new() -> Self {
+        PersonalAccessTokensOptionsBuilder(PersonalAccessTokensOptions::default())
+    }
+
+    /// Only administrators can list tokens belonging to other users.
+    pub fn user_id(&mut self, user_id: u64) -> &mut Self {
+        self.0.params.insert("user_id", user_id.to_string());
+        self
+    }
+
+    /// Either `active` or `inactive`.
+    pub fn state<T>(&mut self, state: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.params.insert("state", state.into());
+        self
+    }
+
+    pub fn revoked(&mut self, revoked: bool) -> &mut Self {
+        self.0.params.insert("revoked", revoked.to_string());
+        self
+    }
+
+    pub fn build(&self) -> PersonalAccessTokensOptions {
+        PersonalAccessTokensOptions {
+            params: self.0.params.clone(),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct ResourceAccessTokensOptions {
+    pub params: HashMap<&'static str, String>,
+}
+
+impl ResourceAccessTokensOptions {
+    pub fn builder() -> ResourceAccessTokensOptionsBuilder {
+        ResourceAccessTokensOptionsBuilder::new()
+    }
+
+    pub fn to_query_string(&self) -> Option<String> {
+        if self.params.is_empty() {
+            None
+        } else {
+            let encoded_qs: String = form_urlencoded::Serializer::new(String::new())
+                .extend_pairs(&self.params)
+                .finish();
+            Some(encoded_qs)
+        }
+    }
+}
+
+pub struct ResourceAccessTokensOptionsBuilder(ResourceAccessTokensOptions);
+
+impl ResourceAccessTokensOptionsBuilder {
+    pub fn new() -> Self {
+        ResourceAccessTokensOptionsBuilder(ResourceAccessTokensOptions::default())
+    }
+
+    /// Either `active` or `inactive`.
+    pub fn state<T>(&mut self, state: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.params.insert("state", state.into());
+        self
+    }
+
+    pub fn build(&self) -> ResourceAccessTokensOptions {
+        ResourceAccessTokensOptions {
+            params: self.0.params.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct AccessTokenParams {
+    pub name: String,
+    pub scopes: Vec<String>,
+    /// Date the token expires, formatted `YYYY-MM-DD`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<String>,
+    /// Only used for project and group access tokens.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub access_level: Option<AccessLevel>,
+}
+
+impl AccessTokenParams {
+    pub fn builder<T: Into<String>>(name: T) -> AccessTokenParamsBuilder {
+        AccessTokenParamsBuilder::new(name)
+    }
+}
+
+pub struct AccessTokenParamsBuilder(AccessTokenParams);
+
+impl AccessTokenParamsBuilder {
+    pub fn new<T>(name: T) -> Self
+        where T: Into<String>
+    {
+        AccessTokenParamsBuilder(AccessTokenParams {
+            name: name.into(),
+            ..Default::default()
+        })
+    }
+
+    /// Add a scope such as `api`, `read_api` or `read_repository`.
+    pub fn scope<T>(&mut self, scope: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.scopes.push(scope.into());
+        self
+    }
+
+    pub fn expires_at<T>(&mut self, date: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.expires_at = Some(date.into());
+        self
+    }
+
+    pub fn access_level(&mut self, access_level: AccessLevel) -> &mut Self {
+        self.0.access_level = Some(access_level);
+        self
+    }
+
+    pub fn build(&self) -> AccessTokenParams {
+        self.0.clone()
+    }
+}
+
+#[derive(Serialize)]
+struct RotateParams {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expires_at: Option<String>,
+}
+
+impl<'a> PersonalAccessTokens<'a> {
+    #[doc(hidden)]
+    pub fn new(gitlab: &'a Gitlab) -> PersonalAccessTokens<'a> {
+        PersonalAccessTokens {
+            gitlab: gitlab,
+        }
+    }
+
+    fn resource(&self, more: &str) -> String {
+        format!("/personal_access_tokens{}", more)
+    }
+
+    pub fn list(&self, options: &PersonalAccessTokensOptions) -> Result<Vec<AccessToken>> {
+        let mut uri = vec![self.resource("")];
+        if let Some(query) = options.to_query_string() {
+            uri.push(query);
+        }
+        self.gitlab.get_all::<AccessToken>(&uri.join("?"))
+    }
+
+    pub fn token(&self, id: u64) -> Result<AccessToken> {
+        self.gitlab.get::<AccessToken>(&self.resource(&format!("/{}", id)))
+    }
+
+    /// The token the client is currently authenticated with.
+    pub fn current(&self) -> Result<AccessToken> {
+        self.gitlab.get::<AccessToken>(&self.resource("/self"))
+    }
+
+    /// Create a token for another user. Requires an administrator.
+    pub fn create(&self, user_id: u64, params: &AccessTokenParams) -> Result<AccessToken> {
+        let json = serde_json::to_string(&params)?;
+        self.gitlab.post::<AccessToken>(
+            &format!("/users/{}/personal_access_tokens", user_id),
+            json.into_bytes()
+        )
+    }
+
+    /// Revoke a token and issue a replacement that expires at `expires_at`.
+    pub fn rotate(&self, id: u64, expires_at: Option<String>) -> Result<AccessToken> {
+        let json = serde_json::to_string(&RotateParams { expires_at: expires_at })?;
+        self.gitlab.post::<AccessToken>(&self.resource(&format!("/{}/rotate", id)), json.into_bytes())
+    }
+
+    pub fn revoke(&self, id: u64) -> Result<()> {
+        self.gitlab.delete(&self.resource(&format!("/{}", id)))
+    }
+
+    /// Revoke the token the client is currently authenticated with.
+    pub fn revoke_current(&self) -> Result<()> {
+        self.gitlab.delete(&self.resource("/self"))
+    }
+}
+
+impl<'a> ResourceAccessTokens<'a> {
+    #[doc(hidden)]
+    pub fn new(gitlab: &'a Gitlab, source: ProjectOrGroup) -> ResourceAccessTokens<'a> {
+        ResourceAccessTokens {
+            gitlab: gitlab,
+            source: source,
+        }
+    }
+
+    fn resource(&self, more: &str) -> String {
+        self.source.resource(&format!("/access_tokens{}", more))
+    }
+
+    pub fn list(&self, options: &ResourceAccessTokensOptions) -> Result<Vec<AccessToken>> {
+        let mut uri = vec![self.resource("")];
+        if let Some(query) = options.to_query_string() {
+            uri.push(query);
+        }
+        self.gitlab.get_all::<AccessToken>(&uri.join("?"))
+    }
+
+    pub fn token(&self, id: u64) -> Result<AccessToken> {
+        self.gitlab.get::<AccessToken>(&self.resource(&format!("/{}", id)))
+    }
+
+    pub fn create(&self, params: &AccessTokenParams) -> Result<AccessToken> {
+        let json = serde_json::to_string(&params)?;
+        self.gitlab.post::<AccessToken>(&self.resource(""), json.into_bytes())
+    }
+
+    /// Revoke a token and issue a replacement that expires at `expires_at`.
+    pub fn rotate(&self, id: u64, expires_at: Option<String>) -> Result<AccessToken> {
+        let json = serde_json::to_string(&RotateParams { expires_at: expires_at })?;
+        self.gitlab.post::<AccessToken>(&self.resource(&format!("/{}/rotate", id)), json.into_bytes())
+    }
+
+    pub fn revoke(&self, id: u64) -> Result<()> {
+        self.gitlab.delete(&self.resource(&format!("/{}", id)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AccessToken, AccessTokenParams, ResourceAccessTokensOptions};
+    use Credentials;
+    use members::AccessLevel;
+    use serde_json;
+
+    const TOKEN: &str = r#"{
+        "id": 42,
+        "name": "deploy",
+        "user_id": 7,
+        "active": true,
+        "revoked": false,
+        "scopes": ["api"],
+        "created_at": "2024-01-01T00:00:00.000Z",
+        "expires_at": "2024-12-31",
+        "last_used_at": null,
+        "access_level": 40,
+        "token": "glpat-secret"
+    }"#;
+
+    #[test]
+    fn token_credentials() {
+        let mut token: AccessToken = serde_json::from_str(TOKEN).unwrap();
+        assert_eq!(token.access_level, Some(AccessLevel::Maintainer));
+        assert_eq!(token.credentials(), Some(Credentials::AccessToken(String::from("glpat-secret"))));
+        assert!(!format!("{:?}", token).contains("glpat-secret"));
+        token.token = None;
+        assert_eq!(token.credentials(), None);
+    }
+
+    #[test]
+    fn params_are_serialized() {
+        let personal = AccessTokenParams::builder("ci")
+            .scope("read_api")
+            .scope("read_repository")
+            .build();
+        assert_eq!(serde_json::to_string(&personal).unwrap(),
+                   r#"{"name":"ci","scopes":["read_api","read_repository"]}"#);
+        let project = AccessTokenParams::builder("bot")
+            .scope("api")
+            .expires_at("2025-01-01")
+            .access_level(AccessLevel::Developer)
+            .build();
+        assert_eq!(serde_json::to_string(&project).unwrap(),
+                   r#"{"name":"bot","scopes":["api"],"expires_at":"2025-01-01","access_level":30}"#);
+    }
+
+    #[test]
+    fn state_is_encoded() {
+        assert_eq!(ResourceAccessTokensOptions::builder().build().to_query_string(), None);
+        let options = ResourceAccessTokensOptions::builder().state("in active").build();
+        assert_eq!(options.to_query_string(), Some(String::from("state=in+active")));
+    }
+}
@@ -0,0 +1,110 @@
+extern crate serde_json;
+
+use self::super::{Gitlab, Error, ErrorKind, Result, ProjectOrGroup};
+use projects::User;
+
+use chrono::{DateTime, Utc};
+
+/// A client for the [Award emoji API](https://docs.gitlab.com/ee/api/award_emoji.html)
+/// of one issue, merge request, snippet or note in a project.
+pub struct AwardEmojis<'a> {
+    gitlab: &'a Gitlab,
+    project: ProjectOrGroup,
+    awardable: Awardable,
+}
+
+/// What emoji are awarded to. Issues and merge requests are identified by
+/// their project-scoped `iid`, snippets and notes by their `id`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Awardable {
+    Issue(u64),
+    MergeRequest(u64),
+    Snippet(u64),
+    /// Issue `iid` and note ID
+    IssueNote(u64, u64),
+    /// Merge request `iid` and note ID
+    MergeRequestNote(u64, u64),
+    /// Snippet ID and note ID
+    SnippetNote(u64, u64),
+}
+
+impl Awardable {
+    fn path(&self) -> String {
+        match *self {
+            Awardable::Issue(iid) => format!("/issues/{}", iid),
+            Awardable::MergeRequest(iid) => format!("/merge_requests/{}", iid),
+            Awardable::Snippet(id) => format!("/snippets/{}", id),
+            Awardable::IssueNote(iid, note) => format!("/issues/{}/notes/{}", iid, note),
+            Awardable::MergeRequestNote(iid, note) => format!("/merge_requests/{}/notes/{}", iid, note),
+            Awardable::SnippetNote(id, note) => format!("/snippets/{}/notes/{}", id, note),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AwardEmoji {
+    pub id: u64,
+    /// Emoji name without colons, e.g. `thumbsup`.
+    pub name: String,
+    pub user: User,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub awardable_id: u64,
+    /// `Issue`, `MergeRequest`, `Snippet` or `Note`
+    pub awardable_type: String,
+}
+
+#[derive(Serialize)]
+struct AwardParams<'b> {
+    name: &'b str,
+}
+
+impl<'a> AwardEmojis<'a> {
+    #[doc(hidden)]
+    pub fn new(gitlab: &'a Gitlab, project: String, awardable: Awardable) -> AwardEmojis<'a> {
+        AwardEmojis {
+            gitlab: gitlab,
+            project: ProjectOrGroup::Project(project),
+            awardable: awardable,
+        }
+    }
+
+    fn resource(&self, more: &str) -> String {
+        self.project.resource(&format!("{}/award_emoji{}", self.awardable.path(), more))
+    }
+
+    pub fn list(&self) -> Result<Vec<AwardEmoji>> {
+        self.gitlab.get_all::<AwardEmoji>(&self.resource(""))
+    }
+
+    pub fn award_emoji(&self, award_id: u64) -> Result<AwardEmoji> {
+        self.gitlab.get::<AwardEmoji>(&self.resource(&format!("/{}", award_id)))
+    }
+
+    /// Award an emoji as the authenticated user.
+    pub fn award<T>(&self, name: T) -> Result<AwardEmoji>
+        where T: Into<String>
+    {
+        let name = name.into();
+        let json = serde_json::to_string(&AwardParams { name: &name })?;
+        self.gitlab.post::<AwardEmoji>(&self.resource(""), json.into_bytes())
+    }
+
+    /// Remove an award. Only the user who awarded it, or an administrator,
+    /// may do so.
+    pub fn delete(&self, award_id: u64) -> Result<()> {
+        self.gitlab.delete(&self.resource(&format!("/{}", award_id)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Awardable;
+
+    #[test]
+    fn awardable_paths() {
+        assert_eq!(Awardable::Issue(4).path(), "/issues/4");
+        assert_eq!(Awardable::MergeRequestNote(2, 31).path(), "/merge_requests/2/notes/31");
+        assert_eq!(Awardable::SnippetNote(8, 1).path(), "/snippets/8/notes/1");
+    }
+}
@@ -0,0 +1,476 @@
+#[macro_use]
+extern crate clap;
+extern crate gitlab;
+#[macro_use]
+extern crate serde_json;
+
+use std::collections::HashMap;
+use std::env;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::process::{self, Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, SyncSender};
+use std::thread;
+use std::time::Duration;
+
+use gitlab::{Error, ErrorKind};
+use gitlab::webhooks::{WebhookEvent, WebhookReceiver, EVENT_HEADER, TOKEN_HEADER};
+
+/// Default for the largest request body accepted, to bound memory per connection.
+const DEFAULT_MAX_BODY: usize = 1024 * 1024;
+/// Longest request or header line accepted.
+const MAX_LINE: u64 = 8 * 1024;
+/// Most headers accepted in one request.
+const MAX_HEADERS: usize = 100;
+/// Most connections handled at once; further ones get a 503.
+const MAX_CONNECTIONS: usize = 32;
+/// Most `--exec` commands running at once.
+const MAX_COMMANDS: usize = 4;
+/// Most deliveries waiting for a command to free up; further ones get a 503.
+const MAX_QUEUED: usize = 32;
+/// How long a client may stall while sending a request or reading the response.
+const IO_TIMEOUT: u64 = 10;
+
+struct Request {
+    method: String,
+    headers: HashMap<String, String>,
+    body: Vec<u8>,
+}
+
+impl Request {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(&name.to_lowercase()).map(|v| v.as_str())
+    }
+}
+
+struct Config {
+    receiver: WebhookReceiver,
+    commands: HashMap<String, String>,
+    max_body: usize,
+}
+
+/// A delivery waiting for its command to run.
+struct Job {
+    command: String,
+    kind: &'static str,
+    body: Vec<u8>,
+}
+
+/// Holds one of the `MAX_CONNECTIONS` handler slots until dropped.
+struct Slot(Arc<AtomicUsize>);
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+fn main() {
+    let matches = clap_app!(app =>
+        (version: crate_version!())
+        (author: crate_authors!())
+        (about: "Listen for Gitlab webhooks and print or dispatch them.\n\n\
+                 Each event is printed to stdout as a line of JSON unless a command is \
+                 configured for its kind, in which case the command runs with the raw \
+                 payload on stdin and the event kind in GITLAB_EVENT.")
+        (@arg listen: -l --listen +takes_value "Address to listen on (default 127.0.0.1:8080)")
+        (@arg token: -t --token +takes_value "Secret token to require (default $GITLAB_WEBHOOK_TOKEN)")
+        (@arg exec: -e --exec +takes_value +multiple
+            "Run a command for an event kind, as KIND=COMMAND (e.g. push='./deploy.sh')")
+        (@arg insecure: --insecure "Allow --exec without a secret token, so anyone can trigger the commands")
+        (@arg limit: --limit +takes_value "Largest payload accepted, in bytes (default 1048576)")
+    ).get_matches();
+
+    let max_body = match matches.value_of("limit").map(|m| m.parse::<usize>()) {
+        Some(Ok(max_body)) => max_body,
+        Some(Err(e)) => {
+            eprintln!("Invalid --limit value: {}", e);
+            process::exit(2);
+        }
+        None => DEFAULT_MAX_BODY,
+    };
+
+    let mut commands = HashMap::new();
+    if let Some(values) = matches.values_of("exec") {
+        for value in values {
+            let mut parts = value.splitn(2, '=');
+            match (parts.next(), parts.next()) {
+                (Some(kind), Some(command)) if !kind.is_empty() => {
+                    commands.insert(kind.to_owned(), command.to_owned());
+                }
+                _ => {
+                    eprintln!("Invalid --exec value '{}', expected KIND=COMMAND", value);
+                    process::exit(2);
+                }
+            }
+        }
+    }
+
+    let receiver = match matches.value_of("token").map(String::from).or(env::var("GITLAB_WEBHOOK_TOKEN").ok()) {
+        Some(token) => WebhookReceiver::with_token(token),
+        None if !commands.is_empty() && !matches.is_present("insecure") => {
+            eprintln!("Refusing to run --exec commands without a secret token; \
+                       set --token or pass --insecure");
+            process::exit(2);
+        }
+        None => {
+            eprintln!("warning: no secret token configured, accepting every delivery");
+            WebhookReceiver::new()
+        }
+    };
+
+    let config = Arc::new(Config {
+        receiver: receiver,
+        commands: commands,
+        max_body: max_body,
+    });
+    let active = Arc::new(AtomicUsize::new(0));
+    let jobs = start_workers();
+
+    let address = matches.value_of("listen").unwrap_or("127.0.0.1:8080");
+    let listener = match TcpListener::bind(address) {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("Unable to listen on {}: {}", address, e);
+            process::exit(2);
+        }
+    };
+    eprintln!("Listening for Gitlab webhooks on {}", address);
+
+    for stream in listener.incoming() {
+        match stream {
+            Ok(mut stream) => {
+                let timeout = Some(Duration::from_secs(IO_TIMEOUT));
+                if let Err(e) = stream.set_read_timeout(timeout).and_then(|_| stream.set_write_timeout(timeout)) {
+                    eprintln!("Unable to set timeouts: {}", e);
+                    continue;
+                }
+
+                if active.fetch_add(1, Ordering::SeqCst) >= MAX_CONNECTIONS {
+                    active.fetch_sub(1, Ordering::SeqCst);
+                    let _ = respond(&mut stream, 503, "Service Unavailable");
+                    continue;
+                }
+                let slot = Slot(active.clone());
+
+                let config = config.clone();
+                let jobs = jobs.clone();
+                thread::spawn(move || {
+                    let _slot = slot;
+                    if let Err(e) = handle(stream, &config, &jobs) {
+                        eprintln!("Connection error: {}", e);
+                    }
+                });
+            }
+            Err(e) => eprintln!("Accept failed: {}", e),
+        }
+    }
+}
+
+/// Start the `MAX_COMMANDS` threads that run commands, returning the queue
+/// that feeds them.
+fn start_workers() -> SyncSender<Job> {
+    let (sender, receiver) = mpsc::sync_channel::<Job>(MAX_QUEUED);
+    let receiver = Arc::new(Mutex::new(receiver));
+    for _ in 0..MAX_COMMANDS {
+        let receiver = receiver.clone();
+        thread::spawn(move || run_jobs(&receiver));
+    }
+    sender
+}
+
+fn run_jobs(receiver: &Mutex<Receiver<Job>>) {
+    loop {
+        let job = match receiver.lock() {
+            Ok(receiver) => receiver.recv(),
+            Err(poisoned) => poisoned.into_inner().recv(),
+        };
+        match job {
+            Ok(job) => {
+                if let Err(e) = run_command(&job.command, job.kind, &job.body) {
+                    eprintln!("Command for {} event failed: {}", job.kind, e);
+                }
+            }
+            Err(_) => return,
+        }
+    }
+}
+
+/// Answer one delivery and print or queue it.
+///
+/// Only a bad token or a malformed request gets a 4xx: GitLab disables
+/// hooks that keep failing, so authenticated deliveries that cannot be
+/// parsed, such as event kinds this crate does not model, are logged and
+/// acknowledged.
+fn handle<S>(mut stream: S, config: &Config, jobs: &SyncSender<Job>) -> io::Result<()>
+    where S: Read + Write,
+{
+    let request = match read_request(&mut stream, config.max_body)? {
+        Some(request) => request,
+        None => return respond(&mut stream, 400, "Bad Request"),
+    };
+
+    if request.method != "POST" {
+        return respond(&mut stream, 405, "Method Not Allowed");
+    }
+
+    let event = config.receiver.receive(
+        request.header(EVENT_HEADER),
+        request.header(TOKEN_HEADER),
+        &request.body,
+    );
+
+    let event = match event {
+        Ok(event) => event,
+        Err(Error(ErrorKind::InvalidWebhookToken, _)) => {
+            eprintln!("Rejected delivery with an invalid token");
+            return respond(&mut stream, 401, "Unauthorized");
+        }
+        Err(_) if request.header(EVENT_HEADER).is_none() => {
+            eprintln!("Rejected delivery without an {} header", EVENT_HEADER);
+            return respond(&mut stream, 400, "Bad Request");
+        }
+        Err(e) => {
+            eprintln!("Ignoring delivery: {}", e);
+            return respond(&mut stream, 202, "Accepted");
+        }
+    };
+
+    let kind = event.kind();
+    match config.commands.get(kind) {
+        Some(command) => {
+            let job = Job {
+                command: command.clone(),
+                kind: kind,
+                body: request.body,
+            };
+            // GitLab gives up on slow hooks, so the command runs after the
+            // response, on one of the bounded workers.
+            match jobs.try_send(job) {
+                Ok(()) => respond(&mut stream, 200, "OK"),
+                Err(_) => {
+                    eprintln!("Too many commands queued, dropping {} event", kind);
+                    respond(&mut stream, 503, "Service Unavailable")
+                }
+            }
+        }
+        None => {
+            respond(&mut stream, 200, "OK")?;
+            let line = json!({ "event": kind, "payload": event });
+            let stdout = io::stdout();
+            let mut out = stdout.lock();
+            writeln!(out, "{}", line)?;
+            out.flush()
+        }
+    }
+}
+
+/// Read one line of at most `MAX_LINE` bytes. Returns `None` at end of
+/// input or if the line is too long.
+fn read_line<R: BufRead>(reader: &mut R) -> io::Result<Option<String>> {
+    let mut line = String::new();
+    reader.take(MAX_LINE).read_line(&mut line)?;
+    if line.ends_with('\n') {
+        Ok(Some(line))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Read a single HTTP/1.1 request. Returns `None` if it is malformed or
+/// larger than allowed.
+fn read_request<R: Read>(stream: R, max_body: usize) -> io::Result<Option<Request>> {
+    let mut reader = BufReader::new(stream);
+
+    let request_line = match read_line(&mut reader)? {
+        Some(line) => line,
+        None => return Ok(None),
+    };
+    let method = match request_line.split_whitespace().next() {
+        Some(method) => method.to_owned(),
+        None => return Ok(None),
+    };
+
+    let mut headers = HashMap::new();
+    loop {
+        let line = match read_line(&mut reader)? {
+            Some(line) => line,
+            None => return Ok(None),
+        };
+        let line = line.trim_right();
+        if line.is_empty() {
+            break;
+        }
+        if headers.len() >= MAX_HEADERS {
+            return Ok(None);
+        }
+        if let Some(index) = line.find(':') {
+            let (name, value) = line.split_at(index);
+            headers.insert(name.trim().to_lowercase(), value[1..].trim().to_owned());
+        }
+    }
+
+    let length = match headers.get("content-length").map(|l| l.parse::<usize>()) {
+        Some(Ok(length)) if length <= max_body => length,
+        Some(_) => return Ok(None),
+        None => 0,
+    };
+    // Grow the buffer as data arrives rather than trusting Content-Length.
+    let mut body = Vec::new();
+    reader.take(length as u64).read_to_end(&mut body)?;
+    if body.len() != length {
+        return Ok(None);
+    }
+
+    Ok(Some(Request {
+        method: method,
+        headers: headers,
+        body: body,
+    }))
+}
+
+fn respond<W: Write>(stream: &mut W, status: u16, reason: &str) -> io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason,
+        reason.len(),
+        reason
+    )
+}
+
+fn run_command(command: &str, kind: &str, body: &[u8]) -> io::Result<()> {
+    let mut child = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .env("GITLAB_EVENT", kind)
+        .stdin(Stdio::piped())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // The command may exit without reading its input; still reap it.
+        if let Err(e) = stdin.write_all(body) {
+            eprintln!("Unable to pass {} payload to command: {}", kind, e);
+        }
+    }
+    let status = child.wait()?;
+    if !status.success() {
+        eprintln!("Command for {} event exited with {}", kind, status);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Config, Job, handle, read_request};
+    use gitlab::webhooks::WebhookReceiver;
+    use std::collections::HashMap;
+    use std::io::{self, Cursor, Read, Write};
+    use std::sync::mpsc::{self, Receiver};
+
+    const PUSH: &'static str = r#"{"object_kind":"push","event_name":"push","before":"95790bf891e76fee5e1747ab589903a6a1f80f22","after":"da1560886d4f094c3e6c9ef40349f7d38b5d27d7","ref":"refs/heads/master","checkout_sha":"da1560886d4f094c3e6c9ef40349f7d38b5d27d7","user_id":4,"user_name":"John Smith","user_username":"jsmith","project_id":15,"project":{"id":15,"name":"Diaspora","description":"","web_url":"http://example.com/mike/diaspora","avatar_url":null,"namespace":"Mike","visibility_level":0,"path_with_namespace":"mike/diaspora","default_branch":"master"},"commits":[],"total_commits_count":0,"repository":{"name":"Diaspora"}}"#;
+
+    /// A connection with a recorded request and a buffer for the response.
+    struct Recorded {
+        request: Cursor<Vec<u8>>,
+        response: Vec<u8>,
+    }
+
+    impl Read for Recorded {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            self.request.read(buf)
+        }
+    }
+
+    impl Write for Recorded {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.response.write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn request(headers: &[(&str, &str)], body: &str) -> Vec<u8> {
+        let mut request = String::from("POST /hook HTTP/1.1\r\nHost: localhost\r\n");
+        for &(name, value) in headers {
+            request.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        request.push_str(&format!("Content-Length: {}\r\n\r\n{}", body.len(), body));
+        request.into_bytes()
+    }
+
+    fn config(push_command: bool) -> Config {
+        let mut commands = HashMap::new();
+        if push_command {
+            commands.insert(String::from("push"), String::from("true"));
+        }
+        Config {
+            receiver: WebhookReceiver::with_token("s3cret"),
+            commands: commands,
+            max_body: 4096,
+        }
+    }
+
+    /// Run `handle` on a recorded request and return the response status.
+    fn status(request: Vec<u8>, config: &Config) -> (u16, Receiver<Job>) {
+        let (sender, receiver) = mpsc::sync_channel(1);
+        let mut connection = Recorded {
+            request: Cursor::new(request),
+            response: Vec::new(),
+        };
+        handle(&mut connection, config, &sender).unwrap();
+        let response = String::from_utf8(connection.response).unwrap();
+        let status = response.split_whitespace().nth(1).unwrap().parse().unwrap();
+        (status, receiver)
+    }
+
+    #[test]
+    fn reads_headers_and_body() {
+        let raw = request(&[("X-Gitlab-Event", "Push Hook")], PUSH);
+        let request = read_request(&raw[..], 4096).unwrap().unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.header("x-gitlab-event"), Some("Push Hook"));
+        assert_eq!(request.body, PUSH.as_bytes());
+    }
+
+    #[test]
+    fn oversized_body_is_rejected() {
+        let raw = request(&[("X-Gitlab-Event", "Push Hook")], PUSH);
+        assert!(read_request(&raw[..], 16).unwrap().is_none());
+        let truncated = b"POST / HTTP/1.1\r\nContent-Length: 100\r\n\r\nshort";
+        assert!(read_request(&truncated[..], 4096).unwrap().is_none());
+    }
+
+    #[test]
+    fn push_is_queued_for_its_command() {
+        let raw = request(&[("X-Gitlab-Event", "Push Hook"), ("X-Gitlab-Token", "s3cret")], PUSH);
+        let (status, jobs) = status(raw, &config(true));
+        assert_eq!(status, 200);
+        assert_eq!(jobs.try_recv().unwrap().kind, "push");
+    }
+
+    #[test]
+    fn missing_event_header_is_bad_request() {
+        let raw = request(&[("X-Gitlab-Token", "s3cret")], PUSH);
+        assert_eq!(status(raw, &config(false)).0, 400);
+    }
+
+    #[test]
+    fn bad_token_is_unauthorized() {
+        let raw = request(&[("X-Gitlab-Event", "Push Hook"), ("X-Gitlab-Token", "wrong")], PUSH);
+        let (status, jobs) = status(raw, &config(true));
+        assert_eq!(status, 401);
+        assert!(jobs.try_recv().is_err());
+    }
+
+    #[test]
+    fn unknown_event_is_acknowledged() {
+        let raw = request(&[("X-Gitlab-Event", "Emoji Hook"), ("X-Gitlab-Token", "s3cret")],
+                          r#"{"object_kind":"emoji"}"#);
+        assert_eq!(status(raw, &config(false)).0, 202);
+    }
+}
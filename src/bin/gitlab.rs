@@ -4,10 +4,22 @@ extern crate gitlab;
 extern crate reqwest;
 
 use std::env;
+use std::fs::File;
+use std::io::{Read, Write};
+use std::process;
+use std::time::Duration;
 use reqwest::Client;
 use gitlab::{Gitlab, Credentials};
+use gitlab::ci_lint::{LintParams, ProjectLintParams};
+use gitlab::gitignores::{self, TemplateCache};
+use gitlab::oauth::{self, OauthClient, LoopbackRedirect, Pkce};
 use gitlab::projects::{SingleProjectOptions, GetProjectUsersOptions, ProjectParams};
 
+const DEFAULT_HOST: &str = "https://gitlab.com";
+
+/// How long fetched templates are used before asking Gitlab again.
+const TEMPLATE_CACHE_TTL: u64 = 7 * 24 * 60 * 60;
+
 fn main() {
     let matches = clap_app!(app =>
         (version: crate_version!())
@@ -19,9 +31,11 @@ fn main() {
             (@arg name: "ID or name of project to retrieve")
         )
         (@subcommand gitignore =>
-            (about: "List or retrieve gitignore templates")
+            (about: "List, retrieve or combine gitignore templates")
             (@arg list: -l --list "Fetch all available gitignore template names")
-            (@arg template: "Name of template to retrieve")
+            (@arg refresh: --refresh "Fetch the template again even if it is cached")
+            (@arg output: -o --output +takes_value "Add the templates to this .gitignore, replacing any earlier block")
+            (@arg template: ... required_unless[list] "Names of templates to retrieve")
         )
         (@subcommand listusers =>
             (about: "List users of a project")
@@ -35,30 +49,104 @@ fn main() {
             (about: "Delete a project")
             (@arg name: "Name of project to delete")
         )
+        (@subcommand lint =>
+            (about: "Validate a .gitlab-ci.yml file, exiting non-zero if it is invalid")
+            (@arg file: "Path to CI configuration (defaults to .gitlab-ci.yml)")
+            (@arg project: -p --project +takes_value "Lint in the context of a project (ID or namespace path)")
+            (@arg dryrun: --dryrun "Simulate pipeline creation when linting against a project")
+            (@arg merged: --merged "Print the merged YAML with includes expanded")
+        )
+        (@subcommand login =>
+            (about: "Obtain an OAuth2 token through the browser and print it")
+            (@arg client_id: -c --client +takes_value +required "OAuth2 application ID")
+            (@arg device: --device "Use the device authorization grant instead of a browser redirect")
+            (@arg port: --port +takes_value "Loopback port for the redirect (must match the application)")
+            (@arg scopes: --scopes +takes_value "Space-separated scopes to request (defaults to api)")
+        )
     ).get_matches();
 
-    let credentials = match env::var("GITLAB_ACCESS_TOKEN") {
-        Ok(token) => Credentials::AccessToken(token),
-        _ => Credentials::Anonymous,
+    let credentials = match (env::var("GITLAB_ACCESS_TOKEN"), env::var("GITLAB_OAUTH_TOKEN")) {
+        (Ok(token), _) => Some(Credentials::AccessToken(token)),
+        (_, Ok(token)) => Some(Credentials::OauthToken(token)),
+        _ => None,
     };
 
-    let host = matches.value_of("host").unwrap_or("https://gitlab.com");
-
-    println!("Gitlab host: {}", host);
-    println!("Credentials used: {:?}", credentials);
+    // Without a host or token of our own, use the instance and job token of
+    // the CI job we are running in, if any.
+    let gitlab = match (matches.value_of("host"), credentials) {
+        (None, None) => Gitlab::from_ci_env(Client::new().unwrap()).unwrap_or_else(|_| {
+            Gitlab::new(DEFAULT_HOST, Client::new().unwrap(), Credentials::Anonymous)
+        }),
+        (host, credentials) => Gitlab::new(
+            host.unwrap_or(DEFAULT_HOST),
+            Client::new().unwrap(),
+            credentials.unwrap_or_default()),
+    };
 
-    let gitlab = Gitlab::new(
-        host,
-        Client::new().unwrap(),
-        credentials);
+    eprintln!("{:?}", gitlab);
 
     if let Some(matches) = matches.subcommand_matches("gitignore") {
-        let gi = gitlab.gitignores();
-        let template = matches.value_of("template").unwrap();
+        let ttl = if matches.is_present("refresh") {
+            Duration::from_secs(0)
+        } else {
+            Duration::from_secs(TEMPLATE_CACHE_TTL)
+        };
+        let gi = match TemplateCache::default_location("gitignores", ttl) {
+            Some(cache) => gitlab.gitignores().with_cache(cache),
+            None => gitlab.gitignores(),
+        };
 
-        let gitignore_template = gi.single_template(template).unwrap();
+        if matches.is_present("list") {
+            match gi.templates() {
+                Ok(templates) => {
+                    for template in templates {
+                        println!("{}\t{}", template.key, template.name);
+                    }
+                }
+                Err(e) => {
+                    eprintln!("Unable to list gitignore templates: {}", e);
+                    process::exit(1);
+                }
+            }
+        } else {
+            let templates: Vec<&str> = matches.values_of("template").unwrap().collect();
+            let content = if templates.len() == 1 && !matches.is_present("output") {
+                gi.single_template(templates[0]).map(|template| template.content.unwrap_or_default())
+            } else {
+                gi.compose(templates.iter().cloned())
+            };
+            let content = match content {
+                Ok(content) => content,
+                Err(e) => {
+                    eprintln!("Unable to fetch gitignore templates {}: {}", templates.join(", "), e);
+                    process::exit(1);
+                }
+            };
 
-        println!("{:?}", gitignore_template);
+            match matches.value_of("output") {
+                Some(path) => {
+                    let mut existing = String::new();
+                    if let Ok(mut file) = File::open(path) {
+                        if let Err(e) = file.read_to_string(&mut existing) {
+                            eprintln!("Unable to read {}: {}", path, e);
+                            process::exit(1);
+                        }
+                    }
+                    let updated = match gitignores::update_gitignore(&existing, &content) {
+                        Ok(updated) => updated,
+                        Err(e) => {
+                            eprintln!("Unable to update {}: {}", path, e);
+                            process::exit(1);
+                        }
+                    };
+                    if let Err(e) = File::create(path).and_then(|mut file| file.write_all(updated.as_bytes())) {
+                        eprintln!("Unable to write {}: {}", path, e);
+                        process::exit(1);
+                    }
+                }
+                None => print!("{}", content),
+            }
+        }
     }
 
     if let Some(matches) = matches.subcommand_matches("getproject") {
@@ -89,4 +177,109 @@ fn main() {
         let name = matches.value_of("name").unwrap();
         println!("{:?}", projects.delete(name));
     }
-}
\ No newline at end of file
+
+    if let Some(matches) = matches.subcommand_matches("lint") {
+        let lint = gitlab.ci_lint();
+        let path = matches.value_of("file").unwrap_or(".gitlab-ci.yml");
+
+        let mut content = String::new();
+        if let Err(e) = File::open(path).and_then(|mut f| f.read_to_string(&mut content)) {
+            eprintln!("Unable to read {}: {}", path, e);
+            process::exit(2);
+        }
+
+        let result = match matches.value_of("project") {
+            Some(project) => {
+                let params = ProjectLintParams::builder(project, content)
+                    .dry_run(matches.is_present("dryrun"))
+                    .build();
+                lint.lint_project(&params)
+            }
+            None => {
+                let params = LintParams::builder(content)
+                    .include_merged_yaml(matches.is_present("merged"))
+                    .build();
+                lint.lint(&params)
+            }
+        };
+
+        match result {
+            Ok(result) => {
+                for warning in &result.warnings {
+                    eprintln!("warning: {}", warning);
+                }
+                for error in &result.errors {
+                    eprintln!("error: {}", error);
+                }
+                if matches.is_present("merged") {
+                    if let Some(ref yaml) = result.merged_yaml {
+                        println!("{}", yaml);
+                    }
+                }
+                if !result.is_valid() {
+                    eprintln!("{} is invalid", path);
+                    process::exit(1);
+                }
+                println!("{} is valid", path);
+            }
+            Err(e) => {
+                eprintln!("Lint request failed: {}", e);
+                process::exit(2);
+            }
+        }
+    }
+
+    if let Some(matches) = matches.subcommand_matches("login") {
+        let client_id = matches.value_of("client_id").unwrap();
+        let scopes: Vec<&str> = matches.value_of("scopes").unwrap_or("api").split_whitespace().collect();
+        let client = OauthClient::new(gitlab.host(), Client::new().unwrap(), client_id);
+
+        let token = if matches.is_present("device") {
+            let device = match client.authorize_device(&scopes) {
+                Ok(device) => device,
+                Err(e) => {
+                    eprintln!("Login failed: {}", e);
+                    process::exit(1);
+                }
+            };
+            eprintln!("Open {} and enter the code {}",
+                device.verification_uri, device.user_code);
+            client.poll_device(&device)
+        } else {
+            let port = match matches.value_of("port").map(|p| (p, p.parse::<u16>())) {
+                Some((_, Ok(port))) => port,
+                Some((p, Err(e))) => {
+                    eprintln!("Invalid port {}: {}", p, e);
+                    process::exit(1);
+                }
+                None => 0,
+            };
+            let redirect = match LoopbackRedirect::bind_port(port) {
+                Ok(redirect) => redirect,
+                Err(e) => {
+                    eprintln!("Unable to listen for the OAuth redirect on port {}: {}", port, e);
+                    process::exit(1);
+                }
+            };
+            let client = client.redirect_uri(redirect.redirect_uri());
+            let pkce = Pkce::new();
+            let state = oauth::random_state();
+            match client.authorize_url(&state, &pkce, &scopes) {
+                Ok(url) => eprintln!("Open this URL in your browser to log in:\n{}", url),
+                Err(e) => {
+                    eprintln!("Login failed: {}", e);
+                    process::exit(1);
+                }
+            }
+            redirect.wait_for_code(&state).and_then(|code| client.exchange_code(&code, &pkce))
+        };
+
+        match token {
+            Ok(token) => println!("{}", token.access_token.expose()),
+            Err(e) => {
+                eprintln!("Login failed: {}", e);
+                process::exit(1);
+            }
+        }
+    }
+}
@@ -0,0 +1,209 @@
+extern crate serde_json;
+
+use self::super::{Gitlab, Error, ErrorKind, Result, ProjectOrGroup};
+use labels::Label;
+use projects::User;
+
+use chrono::NaiveDate;
+
+/// A client for the [Issue boards API](https://docs.gitlab.com/ee/api/boards.html)
+/// of a project or group.
+pub struct Boards<'a> {
+    gitlab: &'a Gitlab,
+    source: ProjectOrGroup,
+}
+
+/// The milestone a board or list is scoped to, as boards report it. Special
+/// filters such as Upcoming and Started have negative IDs.
+#[derive(Debug, Deserialize)]
+pub struct BoardMilestone {
+    pub id: i64,
+    pub title: String,
+    pub iid: Option<u64>,
+    pub state: Option<String>,
+    pub due_date: Option<NaiveDate>,
+    pub web_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Board {
+    pub id: u64,
+    pub name: String,
+    pub milestone: Option<BoardMilestone>,
+    pub assignee: Option<User>,
+    #[serde(default)]
+    pub labels: Vec<Label>,
+    pub weight: Option<u64>,
+    #[serde(default)]
+    pub lists: Vec<BoardList>,
+    #[serde(default)]
+    pub hide_backlog_list: bool,
+    #[serde(default)]
+    pub hide_closed_list: bool,
+}
+
+/// A column of a board. Exactly one of `label`, `assignee` and `milestone`
+/// is set, matching `list_type`.
+#[derive(Debug, Deserialize)]
+pub struct BoardList {
+    pub id: u64,
+    pub label: Option<Label>,
+    pub assignee: Option<User>,
+    pub milestone: Option<BoardMilestone>,
+    pub list_type: Option<String>,
+    pub position: Option<u64>,
+    pub max_issue_count: Option<u64>,
+    pub max_issue_weight: Option<u64>,
+}
+
+/// What the issues in a new board list are selected by.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BoardListKind {
+    Label(u64),
+    Assignee(u64),
+    Milestone(u64),
+}
+
+#[derive(Debug, Default, Serialize)]
+struct CreateListParams {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    label_id: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    assignee_id: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    milestone_id: Option<u64>,
+}
+
+impl From<BoardListKind> for CreateListParams {
+    fn from(kind: BoardListKind) -> CreateListParams {
+        match kind {
+            BoardListKind::Label(id) => CreateListParams { label_id: Some(id), ..Default::default() },
+            BoardListKind::Assignee(id) => CreateListParams { assignee_id: Some(id), ..Default::default() },
+            BoardListKind::Milestone(id) => CreateListParams { milestone_id: Some(id), ..Default::default() },
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct MoveListParams {
+    position: u64,
+}
+
+#[derive(Serialize)]
+struct CreateBoardParams<'b> {
+    name: &'b str,
+}
+
+/// Changes to an existing board; fields left as `None` are not touched.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct EditBoardParams {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Scope the board to issues assigned to this user.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assignee_id: Option<u64>,
+    /// Scope the board to issues in this milestone.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub milestone_id: Option<u64>,
+    /// Comma-separated label names to scope the board to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub labels: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weight: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hide_backlog_list: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hide_closed_list: Option<bool>,
+}
+
+impl<'a> Boards<'a> {
+    #[doc(hidden)]
+    pub fn new(gitlab: &'a Gitlab, source: ProjectOrGroup) -> Boards<'a> {
+        Boards {
+            gitlab: gitlab,
+            source: source,
+        }
+    }
+
+    fn resource(&self, more: &str) -> String {
+        self.source.resource(&format!("/boards{}", more))
+    }
+
+    pub fn list(&self) -> Result<Vec<Board>> {
+        self.gitlab.get_all::<Board>(&self.resource(""))
+    }
+
+    pub fn board(&self, board_id: u64) -> Result<Board> {
+        self.gitlab.get::<Board>(&self.resource(&format!("/{}", board_id)))
+    }
+
+    pub fn create<T>(&self, name: T) -> Result<Board>
+        where T: Into<String>
+    {
+        let name = name.into();
+        let json = serde_json::to_string(&CreateBoardParams { name: &name })?;
+        self.gitlab.post::<Board>(&self.resource(""), json.into_bytes())
+    }
+
+    pub fn edit(&self, board_id: u64, params: &EditBoardParams) -> Result<Board> {
+        let json = serde_json::to_string(&params)?;
+        self.gitlab.put::<Board>(&self.resource(&format!("/{}", board_id)), json.into_bytes())
+    }
+
+    pub fn delete(&self, board_id: u64) -> Result<()> {
+        self.gitlab.delete(&self.resource(&format!("/{}", board_id)))
+    }
+
+    /// The board's lists, without the backlog and closed lists.
+    pub fn lists(&self, board_id: u64) -> Result<Vec<BoardList>> {
+        self.gitlab.get_all::<BoardList>(&self.resource(&format!("/{}/lists", board_id)))
+    }
+
+    pub fn board_list(&self, board_id: u64, list_id: u64) -> Result<BoardList> {
+        self.gitlab.get::<BoardList>(&self.resource(&format!("/{}/lists/{}", board_id, list_id)))
+    }
+
+    pub fn create_list(&self, board_id: u64, kind: BoardListKind) -> Result<BoardList> {
+        let json = serde_json::to_string(&CreateListParams::from(kind))?;
+        self.gitlab.post::<BoardList>(&self.resource(&format!("/{}/lists", board_id)), json.into_bytes())
+    }
+
+    /// Move a list to `position`, counted from 0 among the board's lists.
+    pub fn move_list(&self, board_id: u64, list_id: u64, position: u64) -> Result<BoardList> {
+        let json = serde_json::to_string(&MoveListParams { position: position })?;
+        self.gitlab.put::<BoardList>(
+            &self.resource(&format!("/{}/lists/{}", board_id, list_id)),
+            json.into_bytes()
+        )
+    }
+
+    pub fn delete_list(&self, board_id: u64, list_id: u64) -> Result<()> {
+        self.gitlab.delete(&self.resource(&format!("/{}/lists/{}", board_id, list_id)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Board;
+    use serde_json;
+
+    #[test]
+    fn board_with_special_milestone() {
+        let board: Board = serde_json::from_str(r#"{
+            "id": 1,
+            "name": "Upcoming work",
+            "milestone": { "id": -2, "title": "Upcoming" },
+            "lists": [
+                {
+                    "id": 2,
+                    "label": null,
+                    "milestone": { "id": 12, "title": "10.0" },
+                    "list_type": "milestone",
+                    "position": 0
+                }
+            ]
+        }"#).unwrap();
+        assert_eq!(board.milestone.unwrap().id, -2);
+        assert_eq!(board.lists[0].milestone.as_ref().unwrap().title, "10.0");
+    }
+}
@@ -0,0 +1,274 @@
+extern crate serde_json;
+
+use self::super::{Gitlab, Error, ErrorKind, Result};
+
+use url::percent_encoding::{utf8_percent_encode, PATH_SEGMENT_ENCODE_SET};
+use url::form_urlencoded;
+
+use std::collections::HashMap;
+
+/// A client for the [CI Lint API](https://docs.gitlab.com/ee/api/lint.html)
+pub struct CiLint<'a> {
+    gitlab: &'a Gitlab,
+}
+
+/// Result of validating a `.gitlab-ci.yml` configuration.
+///
+/// The global endpoint reports a `status` of `valid` or `invalid`, while the
+/// project-scoped endpoints report a boolean `valid`. Use `is_valid` rather
+/// than inspecting either field directly.
+#[derive(Debug, Deserialize)]
+pub struct LintResult {
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(default)]
+    pub valid: Option<bool>,
+    #[serde(default)]
+    pub errors: Vec<String>,
+    #[serde(default)]
+    pub warnings: Vec<String>,
+    #[serde(default)]
+    pub merged_yaml: Option<String>,
+}
+
+impl LintResult {
+    pub fn is_valid(&self) -> bool {
+        match (self.valid, self.status.as_ref()) {
+            (Some(valid), _) => valid,
+            (None, Some(status)) => status == "valid",
+            (None, None) => self.errors.is_empty(),
+        }
+    }
+}
+
+/// Parameters for the global `POST /ci/lint` endpoint.
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct LintParams {
+    pub content: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include_merged_yaml: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include_jobs: Option<bool>,
+}
+
+impl LintParams {
+    pub fn builder<T: Into<String>>(content: T) -> LintParamsBuilder {
+        LintParamsBuilder::new(content)
+    }
+}
+
+pub struct LintParamsBuilder(LintParams);
+
+impl LintParamsBuilder {
+    pub fn new<T>(content: T) -> Self
+    where
+        T: Into<String>,
+    {
+        LintParamsBuilder(LintParams {
+            content: content.into(),
+            ..Default::default()
+        })
+    }
+
+    pub fn include_merged_yaml(&mut self, include: bool) -> &mut Self {
+        self.0.include_merged_yaml = Some(include);
+        self
+    }
+
+    pub fn include_jobs(&mut self, include: bool) -> &mut Self {
+        self.0.include_jobs = Some(include);
+        self
+    }
+
+    pub fn build(&self) -> LintParams {
+        self.0.clone()
+    }
+}
+
+/// Parameters for linting content in the context of a project with
+/// `POST /projects/:id/ci/lint`.
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct ProjectLintParams {
+    #[serde(skip_serializing)]
+    pub id: String,
+    pub content: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dry_run: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include_jobs: Option<bool>,
+    #[serde(rename = "ref", skip_serializing_if = "Option::is_none")]
+    pub ref_name: Option<String>,
+}
+
+impl ProjectLintParams {
+    pub fn builder<T, U>(id: T, content: U) -> ProjectLintParamsBuilder
+    where
+        T: Into<String>,
+        U: Into<String>,
+    {
+        ProjectLintParamsBuilder::new(id, content)
+    }
+}
+
+pub struct ProjectLintParamsBuilder(ProjectLintParams);
+
+impl ProjectLintParamsBuilder {
+    pub fn new<T, U>(id: T, content: U) -> Self
+    where
+        T: Into<String>,
+        U: Into<String>,
+    {
+        ProjectLintParamsBuilder(ProjectLintParams {
+            id: id.into(),
+            content: content.into(),
+            ..Default::default()
+        })
+    }
+
+    pub fn dry_run(&mut self, dry_run: bool) -> &mut Self {
+        self.0.dry_run = Some(dry_run);
+        self
+    }
+
+    pub fn include_jobs(&mut self, include: bool) -> &mut Self {
+        self.0.include_jobs = Some(include);
+        self
+    }
+
+    /// Branch or tag used as context when `dry_run` is set.
+    pub fn ref_name<T>(&mut self, ref_name: T) -> &mut Self
+    where
+        T: Into<String>,
+    {
+        self.0.ref_name = Some(ref_name.into());
+        self
+    }
+
+    pub fn build(&self) -> ProjectLintParams {
+        self.0.clone()
+    }
+}
+
+/// Options for validating the configuration already committed to a project
+/// with `GET /projects/:id/ci/lint`.
+#[derive(Default)]
+pub struct ProjectConfigLintOptions {
+    pub id: String,
+    pub params: HashMap<&'static str, String>,
+}
+
+impl ProjectConfigLintOptions {
+    pub fn builder<T: Into<String>>(id: T) -> ProjectConfigLintOptionsBuilder {
+        ProjectConfigLintOptionsBuilder::new(id)
+    }
+
+    pub fn to_query_string(&self) -> Option<String> {
+        if self.params.is_empty() {
+            None
+        } else {
+            let encoded_qs: String = form_urlencoded::Serializer::new(String::new())
+                .extend_pairs(&self.params)
+                .finish();
+            Some(encoded_qs)
+        }
+    }
+}
+
+pub struct ProjectConfigLintOptionsBuilder(ProjectConfigLintOptions);
+
+impl ProjectConfigLintOptionsBuilder {
+    pub fn new<T>(id: T) -> Self
+    where
+        T: Into<String>,
+    {
+        ProjectConfigLintOptionsBuilder(ProjectConfigLintOptions {
+            id: id.into(),
+            ..Default::default()
+        })
+    }
+
+    pub fn dry_run(&mut self, dry_run: bool) -> &mut Self {
+        self.0.params.insert("dry_run", dry_run.to_string());
+        self
+    }
+
+    pub fn include_jobs(&mut self, include: bool) -> &mut Self {
+        self.0.params.insert("include_jobs", include.to_string());
+        self
+    }
+
+    pub fn ref_name<T>(&mut self, ref_name: T) -> &mut Self
+    where
+        T: Into<String>,
+    {
+        self.0.params.insert("ref", ref_name.into());
+        self
+    }
+
+    pub fn build(&self) -> ProjectConfigLintOptions {
+        ProjectConfigLintOptions {
+            id: self.0.id.clone(),
+            params: self.0.params.clone(),
+        }
+    }
+}
+
+impl<'a> CiLint<'a> {
+    #[doc(hidden)]
+    pub fn new(gitlab: &'a Gitlab) -> CiLint<'a> {
+        CiLint {
+            gitlab: gitlab,
+        }
+    }
+
+    fn project_resource(&self, id: &str) -> String {
+        let encoded_id = utf8_percent_encode(id, PATH_SEGMENT_ENCODE_SET).to_string();
+        format!("/projects/{}/ci/lint", encoded_id)
+    }
+
+    /// Validate CI configuration using the instance-wide endpoint.
+    pub fn lint(&self, params: &LintParams) -> Result<LintResult> {
+        let json = serde_json::to_string(&params)?;
+        self.gitlab.post::<LintResult>("/ci/lint", json.into_bytes())
+    }
+
+    /// Validate CI configuration in the context of a project, resolving
+    /// includes and variables as that project would.
+    pub fn lint_project(&self, params: &ProjectLintParams) -> Result<LintResult> {
+        let json = serde_json::to_string(&params)?;
+        self.gitlab.post::<LintResult>(&self.project_resource(&params.id), json.into_bytes())
+    }
+
+    /// Validate the CI configuration on a project's default branch.
+    pub fn lint_project_config(&self, options: &ProjectConfigLintOptions) -> Result<LintResult> {
+        let mut uri = vec![self.project_resource(&options.id)];
+        if let Some(query) = options.to_query_string() {
+            uri.push(query);
+        }
+        self.gitlab.get::<LintResult>(&uri.join("?"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LintResult;
+    use serde_json;
+
+    #[test]
+    fn legacy_status_is_checked() {
+        let rsp: LintResult = serde_json::from_str(
+            r#"{"status": "invalid", "errors": ["jobs config should contain at least one visible job"], "warnings": []}"#
+        ).unwrap();
+        assert!(!rsp.is_valid());
+        assert_eq!(rsp.errors.len(), 1);
+    }
+
+    #[test]
+    fn project_valid_flag_is_checked() {
+        let rsp: LintResult = serde_json::from_str(
+            r#"{"valid": true, "errors": [], "warnings": [], "merged_yaml": "test:\n  script: echo\n"}"#
+        ).unwrap();
+        assert!(rsp.is_valid());
+        assert!(rsp.merged_yaml.is_some());
+    }
+}
@@ -0,0 +1,367 @@
+extern crate serde_json;
+
+use self::super::{Gitlab, Error, ErrorKind, Result};
+use merge_requests::MergeRequest;
+use pipelines::Pipeline;
+use projects::User;
+use repositories::Commit;
+
+use url::percent_encoding::{utf8_percent_encode, PATH_SEGMENT_ENCODE_SET};
+use url::form_urlencoded;
+
+use std::collections::HashMap;
+
+/// A client for the [Deployments API](https://docs.gitlab.com/ee/api/deployments.html)
+pub struct Deployments<'a> {
+    gitlab: &'a Gitlab,
+}
+
+/// The job that performed a deployment.
+#[derive(Debug, Deserialize)]
+pub struct Deployable {
+    pub id: u64,
+    pub status: String,
+    pub stage: String,
+    pub name: String,
+    #[serde(rename = "ref")]
+    pub ref_name: String,
+    pub tag: bool,
+    pub created_at: String,
+    pub started_at: Option<String>,
+    pub finished_at: Option<String>,
+    pub user: Option<User>,
+    pub commit: Option<Commit>,
+    pub pipeline: Option<Pipeline>,
+}
+
+/// The environment as embedded in a deployment, which carries less than
+/// a full `Environment`.
+#[derive(Debug, Deserialize)]
+pub struct DeploymentEnvironment {
+    pub id: u64,
+    pub name: String,
+    pub slug: Option<String>,
+    pub external_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Deployment {
+    pub id: u64,
+    pub iid: u64,
+    #[serde(rename = "ref")]
+    pub ref_name: String,
+    pub sha: String,
+    pub status: Option<String>,
+    pub created_at: String,
+    pub updated_at: Option<String>,
+    pub user: Option<User>,
+    pub environment: Option<DeploymentEnvironment>,
+    pub deployable: Option<Deployable>,
+    #[serde(skip_deserializing)]
+    project: String,
+}
+
+impl Deployment {
+    /// ID or path of the project the deployment belongs to, as given to
+    /// the call that returned it.
+    pub fn project(&self) -> &str {
+        &self.project
+    }
+
+    #[doc(hidden)]
+    pub fn with_project(mut self, project: &str) -> Deployment {
+        self.project = project.to_owned();
+        self
+    }
+
+    /// The commit that was deployed, when the deployment was made by a job.
+    pub fn commit(&self) -> Option<&Commit> {
+        self.deployable.as_ref().and_then(|d| d.commit.as_ref())
+    }
+}
+
+#[derive(Default)]
+pub struct DeploymentsOptions {
+    pub id: String,
+    pub params: HashMap<&'static str, String>,
+}
+
+impl DeploymentsOptions {
+    pub fn builder<T: Into<String>>(id: T) -> DeploymentsOptionsBuilder {
+        DeploymentsOptionsBuilder::new(id)
+    }
+
+    pub fn to_query_string(&self) -> Option<String> {
+        if self.params.is_empty() {
+            None
+        } else {
+            let encoded_qs: String = form_urlencoded::Serializer::new(String::new())
+                .extend_pairs(&self.params)
+                .finish();
+            Some(encoded_qs)
+        }
+    }
+}
+
+pub struct DeploymentsOptionsBuilder(DeploymentsOptions);
+
+impl DeploymentsOptionsBuilder {
+    pub fn new<T>(id: T) -> Self
+        where T: Into<String>,
+    {
+        DeploymentsOptionsBuilder(DeploymentsOptions {
+            id: id.into(),
+            ..Default::default()
+        })
+    }
+
+    pub fn environment<T>(&mut self, environment: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.params.insert("environment", environment.into());
+        self
+    }
+
+    /// One of `created`, `running`, `success`, `failed`, `canceled` or `blocked`.
+    pub fn status<T>(&mut self, status: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.params.insert("status", status.into());
+        self
+    }
+
+    /// ISO 8601 timestamp. GitLab requires `order_by=updated_at` with this filter.
+    pub fn updated_after<T>(&mut self, timestamp: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.params.insert("updated_after", timestamp.into());
+        self
+    }
+
+    /// ISO 8601 timestamp. GitLab requires `order_by=updated_at` with this filter.
+    pub fn updated_before<T>(&mut self, timestamp: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.params.insert("updated_before", timestamp.into());
+        self
+    }
+
+    pub fn order_by<T>(&mut self, order_by: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.params.insert("order_by", order_by.into());
+        self
+    }
+
+    pub fn sort<T>(&mut self, sort: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.params.insert("sort", sort.into());
+        self
+    }
+
+    pub fn build(&self) -> DeploymentsOptions {
+        DeploymentsOptions {
+            id: self.0.id.clone(),
+            params: self.0.params.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct DeploymentParams {
+    pub environment: String,
+    pub sha: String,
+    #[serde(rename = "ref")]
+    pub ref_name: String,
+    pub tag: bool,
+    pub status: String,
+}
+
+impl DeploymentParams {
+    pub fn new<T, U, V, W>(environment: T, sha: U, ref_name: V, status: W) -> Self
+        where T: Into<String>,
+              U: Into<String>,
+              V: Into<String>,
+              W: Into<String>,
+    {
+        DeploymentParams {
+            environment: environment.into(),
+            sha: sha.into(),
+            ref_name: ref_name.into(),
+            tag: false,
+            status: status.into(),
+        }
+    }
+
+    pub fn tag(mut self, tag: bool) -> Self {
+        self.tag = tag;
+        self
+    }
+}
+
+#[derive(Serialize)]
+struct DeploymentStatus {
+    status: String,
+}
+
+impl<'a> Deployments<'a> {
+    #[doc(hidden)]
+    pub fn new(gitlab: &'a Gitlab) -> Deployments<'a> {
+        Deployments {
+            gitlab: gitlab,
+        }
+    }
+
+    fn resource(&self, id: &str, more: &str) -> String {
+        let encoded_id = utf8_percent_encode(id, PATH_SEGMENT_ENCODE_SET).to_string();
+        format!("/projects/{}/deployments{}", encoded_id, more)
+    }
+
+    pub fn list(&self, options: &DeploymentsOptions) -> Result<Vec<Deployment>> {
+        let mut uri = vec![self.resource(&options.id, "")];
+        if let Some(query) = options.to_query_string() {
+            uri.push(query);
+        }
+        let deployments = self.gitlab.get_all::<Deployment>(&uri.join("?"))?;
+        Ok(deployments.into_iter().map(|d| d.with_project(&options.id)).collect())
+    }
+
+    pub fn deployment<T>(&self, id: T, deployment_id: u64) -> Result<Deployment>
+        where T: Into<String>
+    {
+        let id = id.into();
+        self.gitlab.get::<Deployment>(&self.resource(&id, &format!("/{}", deployment_id)))
+            .map(|d| d.with_project(&id))
+    }
+
+    pub fn create<T>(&self, id: T, params: &DeploymentParams) -> Result<Deployment>
+        where T: Into<String>
+    {
+        let id = id.into();
+        let json = serde_json::to_string(&params)?;
+        self.gitlab.post::<Deployment>(&self.resource(&id, ""), json.into_bytes())
+            .map(|d| d.with_project(&id))
+    }
+
+    /// Update the status of a deployment created through the API.
+    pub fn update_status<T, U>(&self, id: T, deployment_id: u64, status: U) -> Result<Deployment>
+        where T: Into<String>,
+              U: Into<String>,
+    {
+        let id = id.into();
+        let json = serde_json::to_string(&DeploymentStatus { status: status.into() })?;
+        self.gitlab.put::<Deployment>(
+            &self.resource(&id, &format!("/{}", deployment_id)),
+            json.into_bytes()
+        ).map(|d| d.with_project(&id))
+    }
+
+    pub fn merge_requests<T>(&self, id: T, deployment_id: u64) -> Result<Vec<MergeRequest>>
+        where T: Into<String>
+    {
+        self.gitlab.get_all::<MergeRequest>(
+            &self.resource(&id.into(), &format!("/{}/merge_requests", deployment_id))
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Deployment;
+    use environments::Environment;
+    use serde_json;
+
+    // From GET /projects/:id/deployments/:deployment_id in the GitLab docs.
+    const DEPLOYMENT: &'static str = r#"{
+        "id": 42,
+        "iid": 2,
+        "ref": "main",
+        "sha": "a91957a858320c0e17f3a0eca7cfacbff50ea29a",
+        "created_at": "2016-08-11T11:32:35.444Z",
+        "updated_at": "2016-08-11T11:34:01.123Z",
+        "status": "success",
+        "user": {
+            "name": "Administrator",
+            "username": "root",
+            "id": 1,
+            "state": "active",
+            "avatar_url": "http://www.gravatar.com/avatar/e64c7d89f26bd1972efa854d13d7dd61?s=80&d=identicon",
+            "web_url": "http://localhost:3000/root"
+        },
+        "environment": {
+            "id": 9,
+            "name": "production",
+            "external_url": "https://about.gitlab.com"
+        },
+        "deployable": {
+            "commit": {
+                "author_email": "admin@example.com",
+                "author_name": "Administrator",
+                "created_at": "2016-08-11T09:36:01.000+01:00",
+                "id": "a91957a858320c0e17f3a0eca7cfacbff50ea29a",
+                "message": "Merge branch 'new-title' into 'main'\r\n\r\nUpdate README\r\n\r\n\r\n\r\nSee merge request !1",
+                "short_id": "a91957a8",
+                "title": "Merge branch 'new-title' into 'main'\r"
+            },
+            "coverage": null,
+            "created_at": "2016-08-11T11:32:24.456Z",
+            "finished_at": "2016-08-11T11:32:35.145Z",
+            "id": 664,
+            "name": "deploy",
+            "ref": "main",
+            "runner": null,
+            "stage": "deploy",
+            "started_at": null,
+            "status": "success",
+            "tag": false,
+            "user": {
+                "id": 1,
+                "name": "Administrator",
+                "username": "root",
+                "state": "active",
+                "avatar_url": "http://www.gravatar.com/avatar/e64c7d89f26bd1972efa854d13d7dd61?s=80&d=identicon",
+                "web_url": "http://gitlab.dev/root"
+            },
+            "pipeline": {
+                "created_at": "2016-08-11T07:43:52.143Z",
+                "id": 42,
+                "ref": "main",
+                "sha": "a91957a858320c0e17f3a0eca7cfacbff50ea29a",
+                "status": "success",
+                "updated_at": "2016-08-11T07:43:52.143Z",
+                "web_url": "http://gitlab.dev/root/project/pipelines/5"
+            }
+        }
+    }"#;
+
+    #[test]
+    fn deployment_embeds_slim_environment() {
+        let deployment: Deployment = serde_json::from_str(DEPLOYMENT).unwrap();
+        let environment = deployment.environment.unwrap();
+        assert_eq!(environment.name, "production");
+        assert_eq!(environment.slug, None);
+        assert_eq!(deployment.deployable.unwrap().commit.unwrap().short_id, "a91957a8");
+    }
+
+    #[test]
+    fn environment_with_last_deployment() {
+        let json = format!(r#"{{
+            "id": 1,
+            "name": "review/fix-foo",
+            "slug": "review-fix-foo-dfjre3",
+            "external_url": "https://review-fix-foo-dfjre3.gitlab.example.com",
+            "state": "available",
+            "tier": "development",
+            "created_at": "2019-05-25T18:55:13.252Z",
+            "updated_at": "2019-05-27T18:55:13.252Z",
+            "last_deployment": {}
+        }}"#, DEPLOYMENT);
+        let environment: Environment = serde_json::from_str(&json).unwrap();
+        let environment = environment.with_project("group/app");
+        assert_eq!(environment.project(), "group/app");
+        let deployment = environment.last_deployment.unwrap();
+        assert_eq!(deployment.iid, 2);
+        assert_eq!(deployment.project(), "group/app");
+    }
+}
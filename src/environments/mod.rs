@@ -0,0 +1,235 @@
+extern crate serde_json;
+
+use self::super::{Gitlab, Error, ErrorKind, Result};
+use deployments::Deployment;
+
+use url::percent_encoding::{utf8_percent_encode, PATH_SEGMENT_ENCODE_SET};
+use url::form_urlencoded;
+
+use std::collections::HashMap;
+
+/// A client for the [Environments API](https://docs.gitlab.com/ee/api/environments.html)
+pub struct Environments<'a> {
+    gitlab: &'a Gitlab,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Environment {
+    pub id: u64,
+    pub name: String,
+    pub slug: String,
+    pub external_url: Option<String>,
+    pub state: String,
+    pub tier: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+    pub last_deployment: Option<Box<Deployment>>,
+    #[serde(skip_deserializing)]
+    project: String,
+}
+
+impl Environment {
+    /// ID or path of the project the environment belongs to, as given to
+    /// the `Environments` call that returned it.
+    pub fn project(&self) -> &str {
+        &self.project
+    }
+
+    #[doc(hidden)]
+    pub fn with_project(mut self, project: &str) -> Environment {
+        self.project = project.to_owned();
+        self.last_deployment = self.last_deployment.map(|d| Box::new(d.with_project(project)));
+        self
+    }
+}
+
+#[derive(Default)]
+pub struct EnvironmentsOptions {
+    pub id: String,
+    pub params: HashMap<&'static str, String>,
+}
+
+impl EnvironmentsOptions {
+    pub fn builder<T: Into<String>>(id: T) -> EnvironmentsOptionsBuilder {
+        EnvironmentsOptionsBuilder::new(id)
+    }
+
+    pub fn to_query_string(&self) -> Option<String> {
+        if self.params.is_empty() {
+            None
+        } else {
+            let encoded_qs: String = form_urlencoded::Serializer::new(String::new())
+                .extend_pairs(&self.params)
+                .finish();
+            Some(encoded_qs)
+        }
+    }
+}
+
+pub struct EnvironmentsOptionsBuilder(EnvironmentsOptions);
+
+impl EnvironmentsOptionsBuilder {
+    pub fn new<T>(id: T) -> Self
+        where T: Into<String>,
+    {
+        EnvironmentsOptionsBuilder(EnvironmentsOptions {
+            id: id.into(),
+            ..Default::default()
+        })
+    }
+
+    pub fn name<T>(&mut self, name: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.params.insert("name", name.into());
+        self
+    }
+
+    pub fn search<T>(&mut self, search: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.params.insert("search", search.into());
+        self
+    }
+
+    /// One of `available`, `stopping` or `stopped`.
+    pub fn states<T>(&mut self, states: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.params.insert("states", states.into());
+        self
+    }
+
+    pub fn build(&self) -> EnvironmentsOptions {
+        EnvironmentsOptions {
+            id: self.0.id.clone(),
+            params: self.0.params.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct EnvironmentParams {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tier: Option<String>,
+}
+
+impl EnvironmentParams {
+    pub fn builder() -> EnvironmentParamsBuilder {
+        EnvironmentParamsBuilder::new()
+    }
+}
+
+pub struct EnvironmentParamsBuilder(EnvironmentParams);
+
+impl EnvironmentParamsBuilder {
+    pub fn new() -> Self {
+        EnvironmentParamsBuilder(EnvironmentParams::default())
+    }
+
+    /// Required when creating an environment; it cannot be changed later.
+    pub fn name<T>(&mut self, name: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.name = Some(name.into());
+        self
+    }
+
+    pub fn external_url<T>(&mut self, url: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.external_url = Some(url.into());
+        self
+    }
+
+    /// One of `production`, `staging`, `testing`, `development` or `other`.
+    pub fn tier<T>(&mut self, tier: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.tier = Some(tier.into());
+        self
+    }
+
+    pub fn build(&self) -> EnvironmentParams {
+        self.0.clone()
+    }
+}
+
+#[derive(Serialize)]
+struct StopParams {
+    force: bool,
+}
+
+impl<'a> Environments<'a> {
+    #[doc(hidden)]
+    pub fn new(gitlab: &'a Gitlab) -> Environments<'a> {
+        Environments {
+            gitlab: gitlab,
+        }
+    }
+
+    fn resource(&self, id: &str, more: &str) -> String {
+        let encoded_id = utf8_percent_encode(id, PATH_SEGMENT_ENCODE_SET).to_string();
+        format!("/projects/{}/environments{}", encoded_id, more)
+    }
+
+    pub fn list(&self, options: &EnvironmentsOptions) -> Result<Vec<Environment>> {
+        let mut uri = vec![self.resource(&options.id, "")];
+        if let Some(query) = options.to_query_string() {
+            uri.push(query);
+        }
+        let environments = self.gitlab.get_all::<Environment>(&uri.join("?"))?;
+        Ok(environments.into_iter().map(|e| e.with_project(&options.id)).collect())
+    }
+
+    pub fn environment<T>(&self, id: T, environment_id: u64) -> Result<Environment>
+        where T: Into<String>
+    {
+        let id = id.into();
+        self.gitlab.get::<Environment>(&self.resource(&id, &format!("/{}", environment_id)))
+            .map(|e| e.with_project(&id))
+    }
+
+    pub fn create<T>(&self, id: T, params: &EnvironmentParams) -> Result<Environment>
+        where T: Into<String>
+    {
+        let id = id.into();
+        let json = serde_json::to_string(&params)?;
+        self.gitlab.post::<Environment>(&self.resource(&id, ""), json.into_bytes())
+            .map(|e| e.with_project(&id))
+    }
+
+    pub fn edit<T>(&self, id: T, environment_id: u64, params: &EnvironmentParams) -> Result<Environment>
+        where T: Into<String>
+    {
+        let id = id.into();
+        let json = serde_json::to_string(&params)?;
+        self.gitlab.put::<Environment>(
+            &self.resource(&id, &format!("/{}", environment_id)),
+            json.into_bytes()
+        ).map(|e| e.with_project(&id))
+    }
+
+    /// Delete an environment. Only stopped environments can be deleted.
+    pub fn delete<T>(&self, id: T, environment_id: u64) -> Result<()>
+        where T: Into<String>
+    {
+        self.gitlab.delete(&self.resource(&id.into(), &format!("/{}", environment_id)))
+    }
+
+    /// Stop an environment, running its `on_stop` job unless `force` is set.
+    pub fn stop<T>(&self, id: T, environment_id: u64, force: bool) -> Result<Environment>
+        where T: Into<String>
+    {
+        let id = id.into();
+        let json = serde_json::to_string(&StopParams { force: force })?;
+        self.gitlab.post::<Environment>(
+            &self.resource(&id, &format!("/{}/stop", environment_id)),
+            json.into_bytes()
+        ).map(|e| e.with_project(&id))
+    }
+}
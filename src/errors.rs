@@ -21,6 +21,30 @@ error_chain! {
             code: StatusCode,
             error: String,
         }
+        SudoWithoutCredentials {
+            description("sudo requires authenticated credentials")
+            display("the Sudo header cannot be sent with anonymous credentials")
+        }
+        MissingCiVariable(name: String) {
+            description("missing CI environment variable")
+            display("CI environment variable {} is not set", name)
+        }
+        Oauth(error: String, description: String) {
+            description("OAuth2 error")
+            display("OAuth2 error {}: {}", error, description)
+        }
+        UnknownWebhookEvent(event: String) {
+            description("unknown webhook event")
+            display("unknown webhook event '{}'", event)
+        }
+        InvalidWebhookToken {
+            description("webhook token does not match")
+            display("webhook token is missing or does not match")
+        }
+        UnbalancedGitignoreMarkers {
+            description("unbalanced gitignore markers")
+            display("the .gitignore has a partial or repeated managed block; fix its marker comments by hand")
+        }
     }
     foreign_links {
         Codec(SerdeError);
@@ -1,38 +1,200 @@
 extern crate serde_json;
 
 use self::super::{Gitlab, Error, ErrorKind, Result};
+use templates::{Templates, TemplateKind};
 
-/// Record representing 
-pub struct GitIgnores<'a> {
-    gitlab: &'a Gitlab,
-}
+use std::collections::HashMap;
+
+pub use templates::{Template, TemplateKey, TemplateCache};
 
-#[derive(Debug, Deserialize)]
-pub struct Template {
-    pub name: String,
-    pub content: Option<String>,
+/// First line of the block written by `update_gitignore`.
+pub const BEGIN_MARKER: &'static str = "# BEGIN gitlab gitignore templates";
+/// Last line of the block written by `update_gitignore`.
+pub const END_MARKER: &'static str = "# END gitlab gitignore templates";
+
+/// A client for the [Gitignore templates API](https://docs.gitlab.com/ee/api/templates/gitignores.html)
+pub struct GitIgnores<'a> {
+    templates: Templates<'a>,
 }
 
 impl<'a> GitIgnores<'a> {
     #[doc(hidden)]
     pub fn new(gitlab: &'a Gitlab, ) -> GitIgnores<'a> {
         GitIgnores {
-            gitlab: gitlab,
+            templates: Templates::new(gitlab, TemplateKind::Gitignores),
         }
     }
 
-    fn resource(&self, more: &str) -> String {
-        format!("/templates/gitignores{}", more)
+    /// Serve templates from `cache` where possible.
+    pub fn with_cache(self, cache: TemplateCache) -> GitIgnores<'a> {
+        GitIgnores {
+            templates: self.templates.with_cache(cache),
+        }
     }
 
-    pub fn templates() {
-
+    /// List the keys and names of every available template.
+    pub fn templates(&self) -> Result<Vec<TemplateKey>> {
+        self.templates.list()
     }
 
     pub fn single_template<T>(&self, name: T) -> Result<Template>
         where T: Into<String> {
-        self.gitlab.get::<Template>(
-            &self.resource(&format!("/{}", name.into()))
-        )
+        self.templates.template(name)
+    }
+
+    /// Fetch several templates and merge them with `compose`.
+    pub fn compose<I, T>(&self, names: I) -> Result<String>
+        where I: IntoIterator<Item = T>,
+              T: Into<String>,
+    {
+        let templates = names.into_iter()
+            .map(|name| self.single_template(name))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(compose(&templates))
+    }
+}
+
+/// Merge templates into one gitignore, each under a `# --- <name> ---` header.
+///
+/// A pattern already written is dropped, unless a pattern of the opposite
+/// polarity (negated or not) has been written since: gitignore lets the last
+/// matching pattern win, so such a repeat is not redundant. Comments and
+/// blank lines are kept as they are.
+pub fn compose(templates: &[Template]) -> String {
+    let mut out = String::new();
+    // Pattern -> number of opposite-polarity patterns written before it.
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    let mut negations = 0;
+    let mut patterns = 0;
+
+    for template in templates {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str(&format!("# --- {} ---\n", template.name));
+
+        let content = template.content.as_ref().map(String::as_str).unwrap_or("");
+        for line in content.lines() {
+            // Only the line ending goes: trailing whitespace can be part of
+            // a pattern when escaped, as in `foo\ `.
+            let pattern = line.trim_right_matches('\r');
+            if pattern.trim().is_empty() || pattern.starts_with('#') {
+                out.push_str(pattern);
+                out.push('\n');
+                continue;
+            }
+
+            let negated = pattern.starts_with('!');
+            let opposite = if negated { patterns } else { negations };
+            if seen.get(pattern) == Some(&opposite) {
+                continue;
+            }
+
+            seen.insert(pattern.to_string(), opposite);
+            if negated {
+                negations += 1;
+            } else {
+                patterns += 1;
+            }
+            out.push_str(pattern);
+            out.push('\n');
+        }
     }
-}
\ No newline at end of file
+
+    out
+}
+
+/// Put `composed` into an existing gitignore between `BEGIN_MARKER` and
+/// `END_MARKER`, replacing the block written last time if there is one and
+/// appending it otherwise. Running it again with the same input is a no-op.
+///
+/// Fails if the markers are unbalanced, e.g. after a hand edit removed
+/// `END_MARKER`, rather than guess which lines belong to the block.
+pub fn update_gitignore(existing: &str, composed: &str) -> Result<String> {
+    let mut block = String::new();
+    block.push_str(BEGIN_MARKER);
+    block.push('\n');
+    block.push_str(composed);
+    if !composed.is_empty() && !composed.ends_with('\n') {
+        block.push('\n');
+    }
+    block.push_str(END_MARKER);
+    block.push('\n');
+
+    let begins = existing.matches(BEGIN_MARKER).count();
+    let ends = existing.matches(END_MARKER).count();
+    let begin = existing.find(BEGIN_MARKER);
+    let end = existing.find(END_MARKER);
+
+    match (begin, end) {
+        (Some(begin), Some(end)) if begins == 1 && ends == 1 && begin < end => {
+            let rest = &existing[end + END_MARKER.len()..];
+            let rest = if rest.starts_with('\n') { &rest[1..] } else { rest };
+            Ok(format!("{}{}{}", &existing[..begin], block, rest))
+        }
+        (None, None) => {
+            let mut out = existing.to_string();
+            if !out.is_empty() {
+                if !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push('\n');
+            }
+            out.push_str(&block);
+            Ok(out)
+        }
+        _ => Err(ErrorKind::UnbalancedGitignoreMarkers.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{compose, update_gitignore, Template};
+
+    fn template(name: &str, content: &str) -> Template {
+        Template {
+            name: name.to_string(),
+            content: Some(content.to_string()),
+        }
+    }
+
+    #[test]
+    fn compose_dedupes_but_keeps_overrides() {
+        let composed = compose(&[
+            template("Rust", "# Build output\n/target/\n*.log\n"),
+            template("Node", "node_modules/\n*.log\n!keep.log\n*.log\n"),
+        ]);
+        assert_eq!(composed,
+                   "# --- Rust ---\n# Build output\n/target/\n*.log\n\n\
+                    # --- Node ---\nnode_modules/\n!keep.log\n*.log\n");
+    }
+
+    #[test]
+    fn update_is_idempotent() {
+        let existing = "/local\n";
+        let once = update_gitignore(existing, "*.log\n").unwrap();
+        assert_eq!(once,
+                   "/local\n\n# BEGIN gitlab gitignore templates\n*.log\n\
+                    # END gitlab gitignore templates\n");
+        assert_eq!(update_gitignore(&once, "*.log\n").unwrap(), once);
+
+        let replaced = update_gitignore(&once, "*.tmp\n").unwrap();
+        assert_eq!(replaced,
+                   "/local\n\n# BEGIN gitlab gitignore templates\n*.tmp\n\
+                    # END gitlab gitignore templates\n");
+    }
+
+    #[test]
+    fn unbalanced_markers_are_rejected() {
+        let dangling = "# BEGIN gitlab gitignore templates\n*.log\n/local\n";
+        assert!(update_gitignore(dangling, "*.log\n").is_err());
+        let reversed = "# END gitlab gitignore templates\n# BEGIN gitlab gitignore templates\n";
+        assert!(update_gitignore(reversed, "*.log\n").is_err());
+    }
+
+    #[test]
+    fn escaped_trailing_space_is_kept() {
+        let composed = compose(&[template("Odd", "foo\\ \r\nfoo\n")]);
+        assert_eq!(composed, "# --- Odd ---\nfoo\\ \nfoo\n");
+    }
+}
@@ -0,0 +1,523 @@
+extern crate serde_json;
+
+use self::super::{Gitlab, Error, ErrorKind, Result};
+use members::AccessLevel;
+use projects::Project;
+
+use url::percent_encoding::{utf8_percent_encode, PATH_SEGMENT_ENCODE_SET};
+use url::form_urlencoded;
+
+use std::collections::HashMap;
+
+/// A client for the [Groups API](https://docs.gitlab.com/ee/api/groups.html)
+pub struct Groups<'a> {
+    gitlab: &'a Gitlab,
+}
+
+/// A group or subgroup. `projects` and `shared_projects` are only filled in
+/// when fetching a single group with projects.
+#[derive(Debug, Deserialize)]
+pub struct Group {
+    pub id: u64,
+    pub name: String,
+    pub path: String,
+    pub description: Option<String>,
+    pub visibility: String,
+    pub full_name: String,
+    pub full_path: String,
+    pub parent_id: Option<u64>,
+    pub web_url: String,
+    pub avatar_url: Option<String>,
+    pub created_at: Option<String>,
+    pub request_access_enabled: Option<bool>,
+    pub lfs_enabled: Option<bool>,
+    #[serde(default)]
+    pub projects: Vec<Project>,
+    #[serde(default)]
+    pub shared_projects: Vec<Project>,
+}
+
+#[derive(Default)]
+pub struct GroupsOptions {
+    pub params: HashMap<&'static str, String>,
+}
+
+impl GroupsOptions {
+    pub fn builder() -> GroupsOptionsBuilder {
+        GroupsOptionsBuilder::new()
+    }
+
+    pub fn to_query_string(&self) -> Option<String> {
+        if self.params.is_empty() {
+            None
+        } else {
+            let encoded_qs: String = form_urlencoded::Serializer::new(String::new())
+                .extend_pairs(&self.params)
+                .finish();
+            Some(encoded_qs)
+        }
+    }
+}
+
+pub struct GroupsOptionsBuilder(GroupsOptions);
+
+impl GroupsOptionsBuilder {
+    pub fn new() -> Self {
+        GroupsOptionsBuilder(GroupsOptions::default())
+    }
+
+    /// Include every group the user can see, not only those they belong to.
+    pub fn all_available(&mut self, all_available: bool) -> &mut Self {
+        self.0.params.insert("all_available", all_available.to_string());
+        self
+    }
+
+    pub fn owned(&mut self, owned: bool) -> &mut Self {
+        self.0.params.insert("owned", owned.to_string());
+        self
+    }
+
+    pub fn min_access_level(&mut self, access_level: AccessLevel) -> &mut Self {
+        self.0.params.insert("min_access_level", access_level.as_u8().to_string());
+        self
+    }
+
+    pub fn search<T>(&mut self, search: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.params.insert("search", search.into());
+        self
+    }
+
+    pub fn top_level_only(&mut self, top_level_only: bool) -> &mut Self {
+        self.0.params.insert("top_level_only", top_level_only.to_string());
+        self
+    }
+
+    pub fn order_by<T>(&mut self, order_by: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.params.insert("order_by", order_by.into());
+        self
+    }
+
+    pub fn sort<T>(&mut self, sort: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.params.insert("sort", sort.into());
+        self
+    }
+
+    pub fn build(&self) -> GroupsOptions {
+        GroupsOptions {
+            params: self.0.params.clone(),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct SingleGroupOptions {
+    pub id: String,
+    pub params: HashMap<&'static str, String>,
+}
+
+impl SingleGroupOptions {
+    pub fn builder<T: Into<String>>(id: T) -> SingleGroupOptionsBuilder {
+        SingleGroupOptionsBuilder::new(id)
+    }
+
+    pub fn to_query_string(&self) -> Option<String> {
+        if self.params.is_empty() {
+            None
+        } else {
+            let encoded_qs: String = form_urlencoded::Serializer::new(String::new())
+                .extend_pairs(&self.params)
+                .finish();
+            Some(encoded_qs)
+        }
+    }
+}
+
+pub struct SingleGroupOptionsBuilder(SingleGroupOptions);
+
+impl SingleGroupOptionsBuilder {
+    pub fn new<T>(id: T) -> Self
+        where T: Into<String>,
+    {
+        SingleGroupOptionsBuilder(SingleGroupOptions {
+            id: id.into(),
+            ..Default::default()
+        })
+    }
+
+    pub fn with_projects(&mut self, with_projects: bool) -> &mut Self {
+        self.0.params.insert("with_projects", with_projects.to_string());
+        self
+    }
+
+    pub fn build(&self) -> SingleGroupOptions {
+        SingleGroupOptions {
+            id: self.0.id.clone(),
+            params: self.0.params.clone(),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct GroupProjectsOptions {
+    pub id: String,
+    pub params: HashMap<&'static str, String>,
+}
+
+impl GroupProjectsOptions {
+    pub fn builder<T: Into<String>>(id: T) -> GroupProjectsOptionsBuilder {
+        GroupProjectsOptionsBuilder::new(id)
+    }
+
+    pub fn to_query_string(&self) -> Option<String> {
+        if self.params.is_empty() {
+            None
+        } else {
+            let encoded_qs: String = form_urlencoded::Serializer::new(String::new())
+                .extend_pairs(&self.params)
+                .finish();
+            Some(encoded_qs)
+        }
+    }
+}
+
+pub struct GroupProjectsOptionsBuilder(GroupProjectsOptions);
+
+impl GroupProjectsOptionsBuilder {
+    pub fn new<T>(id: T) -> Self
+        where T: Into<String>,
+    {
+        GroupProjectsOptionsBuilder(GroupProjectsOptions {
+            id: id.into(),
+            ..Default::default()
+        })
+    }
+
+    /// Include projects from every subgroup, not only direct children.
+    pub fn include_subgroups(&mut self, include_subgroups: bool) -> &mut Self {
+        self.0.params.insert("include_subgroups", include_subgroups.to_string());
+        self
+    }
+
+    pub fn archived(&mut self, archived: bool) -> &mut Self {
+        self.0.params.insert("archived", archived.to_string());
+        self
+    }
+
+    pub fn visibility<T>(&mut self, visibility: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.params.insert("visibility", visibility.into());
+        self
+    }
+
+    pub fn search<T>(&mut self, search: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.params.insert("search", search.into());
+        self
+    }
+
+    pub fn owned(&mut self, owned: bool) -> &mut Self {
+        self.0.params.insert("owned", owned.to_string());
+        self
+    }
+
+    pub fn build(&self) -> GroupProjectsOptions {
+        GroupProjectsOptions {
+            id: self.0.id.clone(),
+            params: self.0.params.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct GroupParams {
+    pub name: String,
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub visibility: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lfs_enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_access_enabled: Option<bool>,
+}
+
+impl GroupParams {
+    pub fn builder<T, U>(name: T, path: U) -> GroupParamsBuilder
+        where T: Into<String>,
+              U: Into<String>,
+    {
+        GroupParamsBuilder::new(name, path)
+    }
+}
+
+pub struct GroupParamsBuilder(GroupParams);
+
+impl GroupParamsBuilder {
+    pub fn new<T, U>(name: T, path: U) -> Self
+        where T: Into<String>,
+              U: Into<String>,
+    {
+        GroupParamsBuilder(GroupParams {
+            name: name.into(),
+            path: path.into(),
+            ..Default::default()
+        })
+    }
+
+    pub fn description<T>(&mut self, description: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.description = Some(description.into());
+        self
+    }
+
+    pub fn visibility<T>(&mut self, visibility: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.visibility = Some(visibility.into());
+        self
+    }
+
+    /// Create the group as a subgroup of `parent_id`.
+    pub fn parent_id(&mut self, parent_id: u64) -> &mut Self {
+        self.0.parent_id = Some(parent_id);
+        self
+    }
+
+    pub fn lfs_enabled(&mut self, lfs_enabled: bool) -> &mut Self {
+        self.0.lfs_enabled = Some(lfs_enabled);
+        self
+    }
+
+    pub fn request_access_enabled(&mut self, enabled: bool) -> &mut Self {
+        self.0.request_access_enabled = Some(enabled);
+        self
+    }
+
+    pub fn build(&self) -> GroupParams {
+        self.0.clone()
+    }
+}
+
+/// Parameters for updating a group; only the fields that are set change.
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct EditGroupParams {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub visibility: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lfs_enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_access_enabled: Option<bool>,
+}
+
+impl EditGroupParams {
+    pub fn builder() -> EditGroupParamsBuilder {
+        EditGroupParamsBuilder::new()
+    }
+}
+
+pub struct EditGroupParamsBuilder(EditGroupParams);
+
+impl EditGroupParamsBuilder {
+    pub fn new() -> Self {
+        EditGroupParamsBuilder(EditGroupParams::default())
+    }
+
+    pub fn name<T>(&mut self, name: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.name = Some(name.into());
+        self
+    }
+
+    pub fn path<T>(&mut self, path: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.path = Some(path.into());
+        self
+    }
+
+    pub fn description<T>(&mut self, description: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.description = Some(description.into());
+        self
+    }
+
+    pub fn visibility<T>(&mut self, visibility: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.visibility = Some(visibility.into());
+        self
+    }
+
+    pub fn lfs_enabled(&mut self, lfs_enabled: bool) -> &mut Self {
+        self.0.lfs_enabled = Some(lfs_enabled);
+        self
+    }
+
+    pub fn request_access_enabled(&mut self, enabled: bool) -> &mut Self {
+        self.0.request_access_enabled = Some(enabled);
+        self
+    }
+
+    pub fn build(&self) -> EditGroupParams {
+        self.0.clone()
+    }
+}
+
+#[derive(Serialize)]
+struct TransferParams {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    group_id: Option<u64>,
+}
+
+impl<'a> Groups<'a> {
+    #[doc(hidden)]
+    pub fn new(gitlab: &'a Gitlab) -> Groups<'a> {
+        Groups {
+            gitlab: gitlab,
+        }
+    }
+
+    fn resource(&self, id: &str, more: &str) -> String {
+        if id.is_empty() {
+            return format!("/groups{}", more);
+        }
+        let encoded_id = utf8_percent_encode(id, PATH_SEGMENT_ENCODE_SET).to_string();
+        format!("/groups/{}{}", encoded_id, more)
+    }
+
+    fn list_resource(&self, base: String, options: &GroupsOptions) -> Result<Vec<Group>> {
+        let mut uri = vec![base];
+        if let Some(query) = options.to_query_string() {
+            uri.push(query);
+        }
+        self.gitlab.get_all::<Group>(&uri.join("?"))
+    }
+
+    pub fn list(&self, options: &GroupsOptions) -> Result<Vec<Group>> {
+        self.list_resource(self.resource("", ""), options)
+    }
+
+    pub fn group(&self, options: &SingleGroupOptions) -> Result<Group> {
+        let mut uri = vec![self.resource(&options.id, "")];
+        if let Some(query) = options.to_query_string() {
+            uri.push(query);
+        }
+        self.gitlab.get::<Group>(&uri.join("?"))
+    }
+
+    pub fn create(&self, params: &GroupParams) -> Result<Group> {
+        let json = serde_json::to_string(&params)?;
+        self.gitlab.post::<Group>(&self.resource("", ""), json.into_bytes())
+    }
+
+    pub fn update<T>(&self, id: T, params: &EditGroupParams) -> Result<Group>
+        where T: Into<String>
+    {
+        let json = serde_json::to_string(&params)?;
+        self.gitlab.put::<Group>(&self.resource(&id.into(), ""), json.into_bytes())
+    }
+
+    pub fn delete<T>(&self, id: T) -> Result<()>
+        where T: Into<String>
+    {
+        self.gitlab.delete(&self.resource(&id.into(), ""))
+    }
+
+    /// Move a group under `parent_id`, or to the top level when `None`.
+    pub fn transfer<T>(&self, id: T, parent_id: Option<u64>) -> Result<Group>
+        where T: Into<String>
+    {
+        let json = serde_json::to_string(&TransferParams { group_id: parent_id })?;
+        self.gitlab.post::<Group>(&self.resource(&id.into(), "/transfer"), json.into_bytes())
+    }
+
+    /// Direct child groups of a group.
+    pub fn subgroups<T>(&self, id: T, options: &GroupsOptions) -> Result<Vec<Group>>
+        where T: Into<String>
+    {
+        self.list_resource(self.resource(&id.into(), "/subgroups"), options)
+    }
+
+    /// Every group nested below a group, at any depth.
+    pub fn descendant_groups<T>(&self, id: T, options: &GroupsOptions) -> Result<Vec<Group>>
+        where T: Into<String>
+    {
+        self.list_resource(self.resource(&id.into(), "/descendant_groups"), options)
+    }
+
+    pub fn projects(&self, options: &GroupProjectsOptions) -> Result<Vec<Project>> {
+        let mut uri = vec![self.resource(&options.id, "/projects")];
+        if let Some(query) = options.to_query_string() {
+            uri.push(query);
+        }
+        self.gitlab.get_all::<Project>(&uri.join("?"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EditGroupParams, Group, GroupParams, GroupsOptions};
+    use members::AccessLevel;
+    use serde_json;
+
+    #[test]
+    fn group_is_deserialized() {
+        let group: Group = serde_json::from_str(r#"{
+            "id": 4,
+            "name": "Tools",
+            "path": "tools",
+            "description": null,
+            "visibility": "private",
+            "full_name": "Platform / Tools",
+            "full_path": "platform/tools",
+            "parent_id": 2,
+            "web_url": "https://gitlab.example.com/groups/platform/tools",
+            "avatar_url": null
+        }"#).unwrap();
+        assert_eq!(group.full_path, "platform/tools");
+        assert_eq!(group.parent_id, Some(2));
+        assert!(group.projects.is_empty());
+    }
+
+    #[test]
+    fn min_access_level_is_numeric() {
+        let options = GroupsOptions::builder()
+            .min_access_level(AccessLevel::Maintainer)
+            .build();
+        assert_eq!(options.to_query_string(), Some(String::from("min_access_level=40")));
+    }
+
+    #[test]
+    fn params_skip_unset_fields() {
+        let params = GroupParams::builder("Tools", "tools").parent_id(2).build();
+        assert_eq!(serde_json::to_string(&params).unwrap(),
+                   r#"{"name":"Tools","path":"tools","parent_id":2}"#);
+        let params = EditGroupParams::builder()
+            .description("Shared tooling")
+            .lfs_enabled(false)
+            .build();
+        assert_eq!(serde_json::to_string(&params).unwrap(),
+                   r#"{"description":"Shared tooling","lfs_enabled":false}"#);
+        assert_eq!(serde_json::to_string(&EditGroupParams::default()).unwrap(), "{}");
+    }
+}
@@ -0,0 +1,357 @@
+extern crate serde_json;
+
+use self::super::{Gitlab, Error, ErrorKind, Result};
+use secret::Secret;
+
+use url::percent_encoding::{utf8_percent_encode, PATH_SEGMENT_ENCODE_SET};
+
+/// A client for [project webhooks](https://docs.gitlab.com/ee/api/projects.html#hooks)
+pub struct ProjectHooks<'a> {
+    gitlab: &'a Gitlab,
+}
+
+/// A client for [system hooks](https://docs.gitlab.com/ee/api/system_hooks.html).
+/// Requires an administrator.
+pub struct SystemHooks<'a> {
+    gitlab: &'a Gitlab,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SystemHook {
+    pub id: u64,
+    pub url: String,
+    pub created_at: String,
+    pub push_events: bool,
+    pub tag_push_events: bool,
+    pub merge_requests_events: bool,
+    pub repository_update_events: bool,
+    pub enable_ssl_verification: bool,
+}
+
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct SystemHookParams {
+    pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<Secret>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub push_events: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag_push_events: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub merge_requests_events: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repository_update_events: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enable_ssl_verification: Option<bool>,
+}
+
+impl SystemHookParams {
+    pub fn builder<T: Into<String>>(url: T) -> SystemHookParamsBuilder {
+        SystemHookParamsBuilder::new(url)
+    }
+}
+
+pub struct SystemHookParamsBuilder(SystemHookParams);
+
+impl SystemHookParamsBuilder {
+    pub fn new<T>(url: T) -> Self
+        where T: Into<String>
+    {
+        SystemHookParamsBuilder(SystemHookParams {
+            url: url.into(),
+            ..Default::default()
+        })
+    }
+
+    pub fn token<T>(&mut self, token: T) -> &mut Self
+        where T: Into<Secret>
+    {
+        self.0.token = Some(token.into());
+        self
+    }
+
+    pub fn push_events(&mut self, enabled: bool) -> &mut Self {
+        self.0.push_events = Some(enabled);
+        self
+    }
+
+    pub fn tag_push_events(&mut self, enabled: bool) -> &mut Self {
+        self.0.tag_push_events = Some(enabled);
+        self
+    }
+
+    pub fn merge_requests_events(&mut self, enabled: bool) -> &mut Self {
+        self.0.merge_requests_events = Some(enabled);
+        self
+    }
+
+    pub fn repository_update_events(&mut self, enabled: bool) -> &mut Self {
+        self.0.repository_update_events = Some(enabled);
+        self
+    }
+
+    pub fn enable_ssl_verification(&mut self, enabled: bool) -> &mut Self {
+        self.0.enable_ssl_verification = Some(enabled);
+        self
+    }
+
+    pub fn build(&self) -> SystemHookParams {
+        self.0.clone()
+    }
+}
+
+/// A project webhook. The secret token is write-only and never returned.
+#[derive(Debug, Deserialize)]
+pub struct ProjectHook {
+    pub id: u64,
+    pub url: String,
+    pub project_id: u64,
+    pub push_events: bool,
+    pub push_events_branch_filter: Option<String>,
+    pub tag_push_events: bool,
+    pub issues_events: bool,
+    #[serde(default)]
+    pub confidential_issues_events: bool,
+    pub merge_requests_events: bool,
+    pub note_events: bool,
+    #[serde(default)]
+    pub confidential_note_events: bool,
+    pub job_events: bool,
+    pub pipeline_events: bool,
+    pub wiki_page_events: bool,
+    #[serde(default)]
+    pub deployment_events: bool,
+    #[serde(default)]
+    pub releases_events: bool,
+    pub enable_ssl_verification: bool,
+    pub created_at: String,
+}
+
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct ProjectHookParams {
+    pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<Secret>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub push_events: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub push_events_branch_filter: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag_push_events: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issues_events: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confidential_issues_events: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub merge_requests_events: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note_events: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confidential_note_events: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub job_events: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pipeline_events: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wiki_page_events: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deployment_events: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub releases_events: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enable_ssl_verification: Option<bool>,
+}
+
+impl ProjectHookParams {
+    pub fn builder<T: Into<String>>(url: T) -> ProjectHookParamsBuilder {
+        ProjectHookParamsBuilder::new(url)
+    }
+}
+
+pub struct ProjectHookParamsBuilder(ProjectHookParams);
+
+impl ProjectHookParamsBuilder {
+    pub fn new<T>(url: T) -> Self
+        where T: Into<String>
+    {
+        ProjectHookParamsBuilder(ProjectHookParams {
+            url: url.into(),
+            ..Default::default()
+        })
+    }
+
+    /// Secret sent back in the `X-Gitlab-Token` header of every delivery.
+    pub fn token<T>(&mut self, token: T) -> &mut Self
+        where T: Into<Secret>
+    {
+        self.0.token = Some(token.into());
+        self
+    }
+
+    pub fn push_events(&mut self, enabled: bool) -> &mut Self {
+        self.0.push_events = Some(enabled);
+        self
+    }
+
+    /// Only deliver push events for branches matching this wildcard.
+    pub fn push_events_branch_filter<T>(&mut self, filter: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.push_events_branch_filter = Some(filter.into());
+        self
+    }
+
+    pub fn tag_push_events(&mut self, enabled: bool) -> &mut Self {
+        self.0.tag_push_events = Some(enabled);
+        self
+    }
+
+    pub fn issues_events(&mut self, enabled: bool) -> &mut Self {
+        self.0.issues_events = Some(enabled);
+        self
+    }
+
+    pub fn confidential_issues_events(&mut self, enabled: bool) -> &mut Self {
+        self.0.confidential_issues_events = Some(enabled);
+        self
+    }
+
+    pub fn merge_requests_events(&mut self, enabled: bool) -> &mut Self {
+        self.0.merge_requests_events = Some(enabled);
+        self
+    }
+
+    pub fn note_events(&mut self, enabled: bool) -> &mut Self {
+        self.0.note_events = Some(enabled);
+        self
+    }
+
+    pub fn confidential_note_events(&mut self, enabled: bool) -> &mut Self {
+        self.0.confidential_note_events = Some(enabled);
+        self
+    }
+
+    pub fn job_events(&mut self, enabled: bool) -> &mut Self {
+        self.0.job_events = Some(enabled);
+        self
+    }
+
+    pub fn pipeline_events(&mut self, enabled: bool) -> &mut Self {
+        self.0.pipeline_events = Some(enabled);
+        self
+    }
+
+    pub fn wiki_page_events(&mut self, enabled: bool) -> &mut Self {
+        self.0.wiki_page_events = Some(enabled);
+        self
+    }
+
+    pub fn deployment_events(&mut self, enabled: bool) -> &mut Self {
+        self.0.deployment_events = Some(enabled);
+        self
+    }
+
+    pub fn releases_events(&mut self, enabled: bool) -> &mut Self {
+        self.0.releases_events = Some(enabled);
+        self
+    }
+
+    pub fn enable_ssl_verification(&mut self, enabled: bool) -> &mut Self {
+        self.0.enable_ssl_verification = Some(enabled);
+        self
+    }
+
+    pub fn build(&self) -> ProjectHookParams {
+        self.0.clone()
+    }
+}
+
+impl<'a> ProjectHooks<'a> {
+    #[doc(hidden)]
+    pub fn new(gitlab: &'a Gitlab) -> ProjectHooks<'a> {
+        ProjectHooks {
+            gitlab: gitlab,
+        }
+    }
+
+    fn resource(&self, id: &str, more: &str) -> String {
+        let encoded_id = utf8_percent_encode(id, PATH_SEGMENT_ENCODE_SET).to_string();
+        format!("/projects/{}/hooks{}", encoded_id, more)
+    }
+
+    pub fn list<T>(&self, id: T) -> Result<Vec<ProjectHook>>
+        where T: Into<String>
+    {
+        self.gitlab.get_all::<ProjectHook>(&self.resource(&id.into(), ""))
+    }
+
+    pub fn hook<T>(&self, id: T, hook_id: u64) -> Result<ProjectHook>
+        where T: Into<String>
+    {
+        self.gitlab.get::<ProjectHook>(&self.resource(&id.into(), &format!("/{}", hook_id)))
+    }
+
+    pub fn add<T>(&self, id: T, params: &ProjectHookParams) -> Result<ProjectHook>
+        where T: Into<String>
+    {
+        let json = serde_json::to_string(&params)?;
+        self.gitlab.post::<ProjectHook>(&self.resource(&id.into(), ""), json.into_bytes())
+    }
+
+    pub fn edit<T>(&self, id: T, hook_id: u64, params: &ProjectHookParams) -> Result<ProjectHook>
+        where T: Into<String>
+    {
+        let json = serde_json::to_string(&params)?;
+        self.gitlab.put::<ProjectHook>(
+            &self.resource(&id.into(), &format!("/{}", hook_id)),
+            json.into_bytes()
+        )
+    }
+
+    pub fn delete<T>(&self, id: T, hook_id: u64) -> Result<()>
+        where T: Into<String>
+    {
+        self.gitlab.delete(&self.resource(&id.into(), &format!("/{}", hook_id)))
+    }
+
+    /// Send a test delivery for `trigger`, which is the name of one of the
+    /// event toggles, e.g. `push_events` or `pipeline_events`.
+    pub fn test<T, U>(&self, id: T, hook_id: u64, trigger: U) -> Result<()>
+        where T: Into<String>,
+              U: Into<String>,
+    {
+        self.gitlab.post::<serde_json::Value>(
+            &self.resource(&id.into(), &format!("/{}/test/{}", hook_id, trigger.into())),
+            Vec::new()
+        )?;
+        Ok(())
+    }
+}
+
+impl<'a> SystemHooks<'a> {
+    #[doc(hidden)]
+    pub fn new(gitlab: &'a Gitlab) -> SystemHooks<'a> {
+        SystemHooks {
+            gitlab: gitlab,
+        }
+    }
+
+    pub fn list(&self) -> Result<Vec<SystemHook>> {
+        self.gitlab.get_all::<SystemHook>("/hooks")
+    }
+
+    pub fn add(&self, params: &SystemHookParams) -> Result<SystemHook> {
+        let json = serde_json::to_string(&params)?;
+        self.gitlab.post::<SystemHook>("/hooks", json.into_bytes())
+    }
+
+    /// Send a sample `project_create` delivery to the hook.
+    pub fn test(&self, hook_id: u64) -> Result<()> {
+        self.gitlab.post::<serde_json::Value>(&format!("/hooks/{}", hook_id), Vec::new())?;
+        Ok(())
+    }
+
+    pub fn delete(&self, hook_id: u64) -> Result<()> {
+        self.gitlab.delete(&format!("/hooks/{}", hook_id))
+    }
+}
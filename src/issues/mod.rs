@@ -1,6 +1,11 @@
 extern crate serde_json;
 
 use self::super::{Gitlab, Error, ErrorKind, Result};
+use milestones::Milestone;
+use projects::User;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use url::percent_encoding::{utf8_percent_encode, PATH_SEGMENT_ENCODE_SET};
 
 /// Struct representing a client for Issues
 pub struct Issues<'a> {
@@ -9,5 +14,40 @@ pub struct Issues<'a> {
 
 #[derive(Debug, Deserialize)]
 pub struct Issue {
-    
-}
\ No newline at end of file
+    pub id: u64,
+    pub iid: u64,
+    pub project_id: u64,
+    pub title: String,
+    pub description: Option<String>,
+    pub state: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub closed_at: Option<DateTime<Utc>>,
+    pub due_date: Option<NaiveDate>,
+    #[serde(default)]
+    pub labels: Vec<String>,
+    pub milestone: Option<Milestone>,
+    pub author: Option<User>,
+    #[serde(default)]
+    pub assignees: Vec<User>,
+    pub weight: Option<u64>,
+    pub confidential: Option<bool>,
+    pub web_url: String,
+}
+
+impl<'a> Issues<'a> {
+    #[doc(hidden)]
+    pub fn new(gitlab: &'a Gitlab) -> Issues<'a> {
+        Issues {
+            gitlab: gitlab,
+        }
+    }
+
+    /// Get a project's issue by its project-scoped `iid`.
+    pub fn issue<T>(&self, id: T, iid: u64) -> Result<Issue>
+        where T: Into<String>
+    {
+        let encoded_id = utf8_percent_encode(&id.into(), PATH_SEGMENT_ENCODE_SET).to_string();
+        self.gitlab.get::<Issue>(&format!("/projects/{}/issues/{}", encoded_id, iid))
+    }
+}
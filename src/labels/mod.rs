@@ -0,0 +1,280 @@
+extern crate serde_json;
+
+use self::super::{Gitlab, Error, ErrorKind, Result, ProjectOrGroup};
+
+use url::percent_encoding::{utf8_percent_encode, PATH_SEGMENT_ENCODE_SET};
+use url::form_urlencoded;
+
+use std::collections::HashMap;
+
+/// A client for the [Labels API](https://docs.gitlab.com/ee/api/labels.html)
+/// of a project or group.
+pub struct Labels<'a> {
+    gitlab: &'a Gitlab,
+    source: ProjectOrGroup,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Label {
+    pub id: u64,
+    pub name: String,
+    pub color: String,
+    pub text_color: Option<String>,
+    pub description: Option<String>,
+    pub description_html: Option<String>,
+    /// Only filled in when listing `with_counts`.
+    pub open_issues_count: Option<u64>,
+    pub closed_issues_count: Option<u64>,
+    pub open_merge_requests_count: Option<u64>,
+    #[serde(default)]
+    pub subscribed: bool,
+    /// Project labels only.
+    pub priority: Option<u64>,
+    pub is_project_label: Option<bool>,
+}
+
+#[derive(Default)]
+pub struct LabelsOptions {
+    pub params: HashMap<&'static str, String>,
+}
+
+impl LabelsOptions {
+    pub fn builder() -> LabelsOptionsBuilder {
+        LabelsOptionsBuilder::new()
+    }
+
+    pub fn to_query_string(&self) -> Option<String> {
+        if self.params.is_empty() {
+            None
+        } else {
+            let encoded_qs: String = form_urlencoded::Serializer::new(String::new())
+                .extend_pairs(&self.params)
+                .finish();
+            Some(encoded_qs)
+        }
+    }
+}
+
+pub struct LabelsOptionsBuilder(LabelsOptions);
+
+impl LabelsOptionsBuilder {
+    pub fn new() -> Self {
+        LabelsOptionsBuilder(LabelsOptions::default())
+    }
+
+    /// Include issue and merge request counts.
+    pub fn with_counts(&mut self, with_counts: bool) -> &mut Self {
+        self.0.params.insert("with_counts", with_counts.to_string());
+        self
+    }
+
+    /// Include labels inherited from ancestor groups.
+    pub fn include_ancestor_groups(&mut self, include: bool) -> &mut Self {
+        self.0.params.insert("include_ancestor_groups", include.to_string());
+        self
+    }
+
+    pub fn search<T>(&mut self, search: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.params.insert("search", search.into());
+        self
+    }
+
+    pub fn build(&self) -> LabelsOptions {
+        LabelsOptions {
+            params: self.0.params.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct LabelParams {
+    pub name: String,
+    /// Either `#RRGGBB` or a CSS color name.
+    pub color: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Project labels only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<u64>,
+}
+
+impl LabelParams {
+    pub fn builder<T, U>(name: T, color: U) -> LabelParamsBuilder
+        where T: Into<String>,
+              U: Into<String>,
+    {
+        LabelParamsBuilder::new(name, color)
+    }
+}
+
+pub struct LabelParamsBuilder(LabelParams);
+
+impl LabelParamsBuilder {
+    pub fn new<T, U>(name: T, color: U) -> Self
+        where T: Into<String>,
+              U: Into<String>,
+    {
+        LabelParamsBuilder(LabelParams {
+            name: name.into(),
+            color: color.into(),
+            ..Default::default()
+        })
+    }
+
+    pub fn description<T>(&mut self, description: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.description = Some(description.into());
+        self
+    }
+
+    pub fn priority(&mut self, priority: u64) -> &mut Self {
+        self.0.priority = Some(priority);
+        self
+    }
+
+    pub fn build(&self) -> LabelParams {
+        self.0.clone()
+    }
+}
+
+/// Changes to an existing label; fields left as `None` are not touched.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct EditLabelParams {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<u64>,
+}
+
+/// Labels are addressed by ID or by name, which may contain spaces or `/`.
+fn label_resource(source: &ProjectOrGroup, label: &str, more: &str) -> String {
+    source.resource(&format!("/labels/{}{}", utf8_percent_encode(label, PATH_SEGMENT_ENCODE_SET), more))
+}
+
+impl<'a> Labels<'a> {
+    #[doc(hidden)]
+    pub fn new(gitlab: &'a Gitlab, source: ProjectOrGroup) -> Labels<'a> {
+        Labels {
+            gitlab: gitlab,
+            source: source,
+        }
+    }
+
+    fn resource(&self, more: &str) -> String {
+        self.source.resource(&format!("/labels{}", more))
+    }
+
+    fn label_resource(&self, label: &str, more: &str) -> String {
+        label_resource(&self.source, label, more)
+    }
+
+    pub fn list(&self, options: &LabelsOptions) -> Result<Vec<Label>> {
+        let mut uri = vec![self.resource("")];
+        if let Some(query) = options.to_query_string() {
+            uri.push(query);
+        }
+        self.gitlab.get_all::<Label>(&uri.join("?"))
+    }
+
+    pub fn label<T>(&self, label: T) -> Result<Label>
+        where T: Into<String>
+    {
+        self.gitlab.get::<Label>(&self.label_resource(&label.into(), ""))
+    }
+
+    pub fn create(&self, params: &LabelParams) -> Result<Label> {
+        let json = serde_json::to_string(&params)?;
+        self.gitlab.post::<Label>(&self.resource(""), json.into_bytes())
+    }
+
+    pub fn edit<T>(&self, label: T, params: &EditLabelParams) -> Result<Label>
+        where T: Into<String>
+    {
+        let json = serde_json::to_string(&params)?;
+        self.gitlab.put::<Label>(&self.label_resource(&label.into(), ""), json.into_bytes())
+    }
+
+    pub fn delete<T>(&self, label: T) -> Result<()>
+        where T: Into<String>
+    {
+        self.gitlab.delete(&self.label_resource(&label.into(), ""))
+    }
+
+    /// Subscribe the authenticated user to issues and merge requests with
+    /// the label. Returns `None` if already subscribed.
+    pub fn subscribe<T>(&self, label: T) -> Result<Option<Label>>
+        where T: Into<String>
+    {
+        self.gitlab.post_if_modified::<Label>(&self.label_resource(&label.into(), "/subscribe"), Vec::new())
+    }
+
+    /// Returns `None` if the user was not subscribed.
+    pub fn unsubscribe<T>(&self, label: T) -> Result<Option<Label>>
+        where T: Into<String>
+    {
+        self.gitlab.post_if_modified::<Label>(&self.label_resource(&label.into(), "/unsubscribe"), Vec::new())
+    }
+
+    /// Turn a project label into a label of the project's group. Only valid
+    /// for project labels.
+    pub fn promote<T>(&self, label: T) -> Result<Label>
+        where T: Into<String>
+    {
+        self.gitlab.put::<Label>(&self.label_resource(&label.into(), "/promote"), Vec::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Label, LabelParams, label_resource};
+    use ProjectOrGroup;
+    use serde_json;
+
+    #[test]
+    fn label_is_deserialized() {
+        let label: Label = serde_json::from_str(r#"{
+            "id": 1,
+            "name": "bug",
+            "color": "#d9534f",
+            "text_color": "#FFFFFF",
+            "description": "Bug reported by user",
+            "description_html": "Bug reported by user",
+            "open_issues_count": 1,
+            "closed_issues_count": 0,
+            "open_merge_requests_count": 1,
+            "subscribed": false,
+            "priority": 10,
+            "is_project_label": true
+        }"#).unwrap();
+        assert_eq!(label.name, "bug");
+        assert_eq!(label.priority, Some(10));
+        assert_eq!(label.open_issues_count, Some(1));
+    }
+
+    #[test]
+    fn label_names_are_encoded() {
+        let project = ProjectOrGroup::Project(String::from("group/app"));
+        assert_eq!(label_resource(&project, "needs review", "/subscribe"),
+                   "/projects/group%2Fapp/labels/needs%20review/subscribe");
+        assert_eq!(label_resource(&project, "scope/value", ""),
+                   "/projects/group%2Fapp/labels/scope%2Fvalue");
+        let group = ProjectOrGroup::Group(String::from("7"));
+        assert_eq!(label_resource(&group, "12", ""), "/groups/7/labels/12");
+    }
+
+    #[test]
+    fn params_skip_unset_fields() {
+        let params = LabelParams::builder("bug", "#d9534f").build();
+        assert_eq!(serde_json::to_string(&params).unwrap(), r##"{"name":"bug","color":"#d9534f"}"##);
+        let params = LabelParams::builder("bug", "red").description("Broken").priority(1).build();
+        assert_eq!(serde_json::to_string(&params).unwrap(),
+                   r#"{"name":"bug","color":"red","description":"Broken","priority":1}"#);
+    }
+}
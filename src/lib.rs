@@ -13,31 +13,91 @@ extern crate serde_json;
 
 extern crate url;
 
+extern crate base64;
+extern crate chrono;
+extern crate rand;
+extern crate sha2;
+
 pub mod errors;
+pub mod access_tokens;
+pub mod award_emoji;
+pub mod boards;
 pub mod projects;
 pub mod gitignores;
+pub mod groups;
+pub mod hooks;
+pub mod issues;
+pub mod labels;
+pub mod members;
+pub mod ci_lint;
+pub mod pipelines;
+pub mod environments;
+pub mod deployments;
+pub mod merge_requests;
+pub mod milestones;
+pub mod namespaces;
+pub mod oauth;
+pub mod repositories;
+pub mod runners;
+pub mod secret;
+pub mod templates;
+pub mod users;
+pub mod variables;
+pub mod webhooks;
 
-//pub mod ci_lint;
 pub use errors::{Error, ErrorKind, Result};
+pub use secret::Secret;
 
 use serde::de::DeserializeOwned;
 
+use access_tokens::{PersonalAccessTokens, ResourceAccessTokens};
+use award_emoji::{AwardEmojis, Awardable};
+use boards::Boards;
+use ci_lint::CiLint;
+use deployments::Deployments;
+use environments::Environments;
 use gitignores::GitIgnores;
+use groups::Groups;
+use hooks::{ProjectHooks, SystemHooks};
+use issues::Issues;
+use labels::Labels;
+use members::Members;
+use milestones::{Milestones, Iterations};
+use namespaces::Namespaces;
+use oauth::{OauthClient, OauthToken};
+use pipelines::{PipelineSchedules, PipelineTriggers};
 use projects::Projects;
+use runners::Runners;
+use templates::{Templates, TemplateKind, Licenses, ProjectTemplates};
+use users::Users;
+use variables::{Variables, VariableScope};
 
-use reqwest::Body;
 use reqwest::Client;
+use reqwest::Response;
 use reqwest::StatusCode;
 use reqwest::Method;
-use reqwest::header::{Headers, Authorization, Bearer, ContentType, Accept, qitem};
+use reqwest::header::{Headers, Authorization, Basic, Bearer, ContentType, Accept, qitem};
 use reqwest::mime;
 
-use std::io::Read;
+use url::form_urlencoded;
+use url::percent_encoding::{utf8_percent_encode, PATH_SEGMENT_ENCODE_SET};
+
+use std::env;
+use std::fmt;
+use std::sync::Mutex;
 
 header! { (GitlabPrivateToken, "PRIVATE-TOKEN") => [String] }
+header! { (GitlabSudo, "Sudo") => [String] }
+header! { (GitlabJobToken, "JOB-TOKEN") => [String] }
+header! { (GitlabNextPage, "X-Next-Page") => [String] }
+
+/// Page size requested when following pagination.
+const PER_PAGE: u32 = 100;
+
+const API_PATH: &str = "/api/v4";
 
 /// Credentials for Gitlab authentication
-#[derive(Debug, PartialEq)]
+#[derive(Clone, PartialEq)]
 pub enum Credentials {
     /// Unauthenticated anonymous access,
     Anonymous,
@@ -46,7 +106,40 @@ pub enum Credentials {
     OauthToken(String),
 
     /// Personal Access / Private Token
-    AccessToken(String)
+    AccessToken(String),
+
+    /// CI job token, as provided to jobs in `CI_JOB_TOKEN`
+    JobToken(String),
+
+    /// Deploy token, sent with basic authentication
+    DeployToken {
+        username: String,
+        token: String,
+    },
+}
+
+impl fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Credentials::Anonymous => f.write_str("Anonymous"),
+            Credentials::OauthToken(_) => f.write_str("OauthToken([REDACTED])"),
+            Credentials::AccessToken(_) => f.write_str("AccessToken([REDACTED])"),
+            Credentials::JobToken(_) => f.write_str("JobToken([REDACTED])"),
+            Credentials::DeployToken { ref username, .. } => {
+                write!(f, "DeployToken {{ username: {:?}, token: [REDACTED] }}", username)
+            }
+        }
+    }
+}
+
+impl Credentials {
+    /// Job token credentials from `CI_JOB_TOKEN`, for code running inside a
+    /// GitLab CI job.
+    pub fn from_ci_env() -> Result<Credentials> {
+        env::var("CI_JOB_TOKEN")
+            .map(Credentials::JobToken)
+            .map_err(|_| ErrorKind::MissingCiVariable(String::from("CI_JOB_TOKEN")).into())
+    }
 }
 
 impl Default for Credentials {
@@ -55,11 +148,118 @@ impl Default for Credentials {
     }
 }
 
+/// User to act as through the `Sudo` header. Requires an administrator's
+/// credentials.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SudoUser {
+    Username(String),
+    Id(u64),
+}
+
+impl fmt::Display for SudoUser {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SudoUser::Username(ref username) => f.write_str(username),
+            SudoUser::Id(id) => write!(f, "{}", id),
+        }
+    }
+}
+
+impl From<u64> for SudoUser {
+    fn from(id: u64) -> SudoUser {
+        SudoUser::Id(id)
+    }
+}
+
+impl From<String> for SudoUser {
+    fn from(username: String) -> SudoUser {
+        SudoUser::Username(username)
+    }
+}
+
+impl<'a> From<&'a str> for SudoUser {
+    fn from(username: &'a str) -> SudoUser {
+        SudoUser::Username(username.to_owned())
+    }
+}
+
+/// A project or group that a client works on, such as the owner of a set
+/// of labels or members.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProjectOrGroup {
+    /// Project ID or namespaced path
+    Project(String),
+
+    /// Group ID or full path
+    Group(String),
+}
+
+impl ProjectOrGroup {
+    /// `/projects/:id` or `/groups/:id` with the ID percent-encoded,
+    /// followed by `more`.
+    fn resource(&self, more: &str) -> String {
+        match *self {
+            ProjectOrGroup::Project(ref id) => format!(
+                "/projects/{}{}",
+                utf8_percent_encode(id, PATH_SEGMENT_ENCODE_SET),
+                more
+            ),
+            ProjectOrGroup::Group(ref id) => format!(
+                "/groups/{}{}",
+                utf8_percent_encode(id, PATH_SEGMENT_ENCODE_SET),
+                more
+            ),
+        }
+    }
+}
+
+/// The instance URL from `CI_SERVER_URL` or, failing that, `CI_API_V4_URL`
+/// with its API path removed.
+fn ci_host<F>(var: F) -> Result<String>
+    where F: Fn(&str) -> Option<String> {
+    let host = match var("CI_SERVER_URL") {
+        Some(url) => url,
+        None => {
+            let api_url = var("CI_API_V4_URL")
+                .ok_or_else(|| {
+                    Error::from(ErrorKind::MissingCiVariable(String::from("CI_SERVER_URL or CI_API_V4_URL")))
+                })?;
+            api_url.trim_right_matches('/').trim_right_matches(API_PATH).to_owned()
+        }
+    };
+    Ok(host.trim_right_matches('/').to_owned())
+}
+
+/// An `application/x-www-form-urlencoded` request body.
+fn form_body(params: &[(String, String)]) -> String {
+    form_urlencoded::Serializer::new(String::new())
+        .extend_pairs(params)
+        .finish()
+}
+
 /// Gitlab API client
 pub struct Gitlab {
     host: String,
     http: Client,
     credentials: Credentials,
+    sudo: Option<SudoUser>,
+    oauth: Option<Mutex<OauthSession>>,
+}
+
+impl fmt::Debug for Gitlab {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Gitlab")
+            .field("host", &self.host)
+            .field("credentials", &self.credentials)
+            .field("sudo", &self.sudo)
+            .finish()
+    }
+}
+
+/// An OAuth2 token together with the application that can refresh it.
+struct OauthSession {
+    client: OauthClient,
+    token: OauthToken,
 }
 
 impl Gitlab {
@@ -67,25 +267,254 @@ impl Gitlab {
     pub fn new<T>(host: T, http: Client, credentials: Credentials) -> Gitlab
         where T: Into<String> {
         Gitlab {
-            host: host.into() + "/api/v4",
+            host: host.into() + API_PATH,
             http: http,
             credentials: credentials,
+            sudo: None,
+            oauth: None,
         }
     }
 
+    /// Create a client authenticated with an OAuth2 token that is refreshed
+    /// automatically through `oauth` once it expires.
+    pub fn with_oauth<T>(host: T, http: Client, oauth: OauthClient, token: OauthToken) -> Gitlab
+        where T: Into<String> {
+        let credentials = Credentials::OauthToken(token.access_token.expose().to_owned());
+        let mut gitlab = Gitlab::new(host, http, credentials);
+        gitlab.oauth = Some(Mutex::new(OauthSession {
+            client: oauth,
+            token: token,
+        }));
+        gitlab
+    }
+
+    /// URL of the Gitlab instance, without the API path.
+    pub fn host(&self) -> &str {
+        &self.host[..self.host.len() - API_PATH.len()]
+    }
+
+    /// The OAuth2 token currently in use, which may differ from the one the
+    /// client was created with if it has been refreshed.
+    pub fn oauth_token(&self) -> Option<OauthToken> {
+        self.oauth.as_ref().and_then(|session| {
+            session.lock().ok().map(|session| session.token.clone())
+        })
+    }
+
+    /// The credentials to send, refreshing an expired OAuth2 token first.
+    fn current_credentials(&self) -> Result<Credentials> {
+        let session = match self.oauth {
+            Some(ref session) => session,
+            None => return Ok(self.credentials.clone()),
+        };
+        let mut session = match session.lock() {
+            Ok(session) => session,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        if session.token.is_expired() {
+            if let Some(refresh_token) = session.token.refresh_token.clone() {
+                debug!("Refreshing expired OAuth2 token");
+                session.token = session.client.refresh(&refresh_token)?;
+            }
+        }
+        Ok(Credentials::OauthToken(session.token.access_token.expose().to_owned()))
+    }
+
+    /// Create a client for the GitLab instance running the current CI job,
+    /// authenticated with the job token.
+    ///
+    /// The host is taken from `CI_SERVER_URL`, falling back to
+    /// `CI_API_V4_URL`.
+    pub fn from_ci_env(http: Client) -> Result<Gitlab> {
+        let credentials = Credentials::from_ci_env()?;
+        let host = ci_host(|name| env::var(name).ok())?;
+        Ok(Gitlab::new(host, http, credentials))
+    }
+
+    /// Perform every request made through this client as another user.
+    pub fn set_sudo<T>(&mut self, user: Option<T>) -> Result<()>
+        where T: Into<SudoUser> {
+        let user = user.map(|u| u.into());
+        if user.is_some() && self.credentials == Credentials::Anonymous {
+            return Err(ErrorKind::SudoWithoutCredentials.into());
+        }
+        self.sudo = user;
+        Ok(())
+    }
+
+    /// A handle that performs requests as another user, leaving this client
+    /// unchanged. An OAuth2 token is copied into the handle as it is now and
+    /// is not refreshed by it.
+    pub fn sudo<T>(&self, user: T) -> Result<Gitlab>
+        where T: Into<SudoUser> {
+        let mut gitlab = Gitlab {
+            host: self.host.clone(),
+            http: self.http.clone(),
+            credentials: self.current_credentials()?,
+            sudo: None,
+            oauth: None,
+        };
+        gitlab.set_sudo(Some(user))?;
+        Ok(gitlab)
+    }
+
+    pub fn personal_access_tokens(&self) -> PersonalAccessTokens {
+        PersonalAccessTokens::new(self)
+    }
+
+    pub fn project_access_tokens<T>(&self, id: T) -> ResourceAccessTokens
+        where T: Into<String> {
+        ResourceAccessTokens::new(self, ProjectOrGroup::Project(id.into()))
+    }
+
+    pub fn group_access_tokens<T>(&self, id: T) -> ResourceAccessTokens
+        where T: Into<String> {
+        ResourceAccessTokens::new(self, ProjectOrGroup::Group(id.into()))
+    }
+
+    pub fn award_emoji<T>(&self, project: T, awardable: Awardable) -> AwardEmojis
+        where T: Into<String> {
+        AwardEmojis::new(self, project.into(), awardable)
+    }
+
+    pub fn project_boards<T>(&self, id: T) -> Boards
+        where T: Into<String> {
+        Boards::new(self, ProjectOrGroup::Project(id.into()))
+    }
+
+    pub fn group_boards<T>(&self, id: T) -> Boards
+        where T: Into<String> {
+        Boards::new(self, ProjectOrGroup::Group(id.into()))
+    }
+
+    pub fn ci_lint(&self) -> CiLint {
+        CiLint::new(self)
+    }
+
+    pub fn deployments(&self) -> Deployments {
+        Deployments::new(self)
+    }
+
+    pub fn environments(&self) -> Environments {
+        Environments::new(self)
+    }
+
     pub fn gitignores(&self) -> GitIgnores {
         GitIgnores::new(self)
     }
 
+    pub fn groups(&self) -> Groups {
+        Groups::new(self)
+    }
+
+    pub fn project_hooks(&self) -> ProjectHooks {
+        ProjectHooks::new(self)
+    }
+
+    pub fn issues(&self) -> Issues {
+        Issues::new(self)
+    }
+
+    pub fn project_labels<T>(&self, id: T) -> Labels
+        where T: Into<String> {
+        Labels::new(self, ProjectOrGroup::Project(id.into()))
+    }
+
+    pub fn group_labels<T>(&self, id: T) -> Labels
+        where T: Into<String> {
+        Labels::new(self, ProjectOrGroup::Group(id.into()))
+    }
+
+    pub fn project_members<T>(&self, id: T) -> Members
+        where T: Into<String> {
+        Members::new(self, ProjectOrGroup::Project(id.into()))
+    }
+
+    pub fn group_members<T>(&self, id: T) -> Members
+        where T: Into<String> {
+        Members::new(self, ProjectOrGroup::Group(id.into()))
+    }
+
+    pub fn project_milestones<T>(&self, id: T) -> Milestones
+        where T: Into<String> {
+        Milestones::new(self, ProjectOrGroup::Project(id.into()))
+    }
+
+    pub fn group_milestones<T>(&self, id: T) -> Milestones
+        where T: Into<String> {
+        Milestones::new(self, ProjectOrGroup::Group(id.into()))
+    }
+
+    pub fn project_iterations<T>(&self, id: T) -> Iterations
+        where T: Into<String> {
+        Iterations::new(self, ProjectOrGroup::Project(id.into()))
+    }
+
+    pub fn group_iterations<T>(&self, id: T) -> Iterations
+        where T: Into<String> {
+        Iterations::new(self, ProjectOrGroup::Group(id.into()))
+    }
+
+    pub fn namespaces(&self) -> Namespaces {
+        Namespaces::new(self)
+    }
+
+    pub fn pipeline_schedules(&self) -> PipelineSchedules {
+        PipelineSchedules::new(self)
+    }
+
+    pub fn pipeline_triggers(&self) -> PipelineTriggers {
+        PipelineTriggers::new(self)
+    }
+
     pub fn projects(&self) -> Projects {
         Projects::new(self)
     }
 
-    fn request_headers(&self) -> Headers {
+    pub fn runners(&self) -> Runners {
+        Runners::new(self)
+    }
+
+    pub fn system_hooks(&self) -> SystemHooks {
+        SystemHooks::new(self)
+    }
+
+    pub fn templates(&self, kind: TemplateKind) -> Templates {
+        Templates::new(self, kind)
+    }
+
+    pub fn licenses(&self) -> Licenses {
+        Licenses::new(self)
+    }
+
+    pub fn project_templates(&self) -> ProjectTemplates {
+        ProjectTemplates::new(self)
+    }
+
+    pub fn users(&self) -> Users {
+        Users::new(self)
+    }
+
+    pub fn project_variables<T>(&self, id: T) -> Variables
+        where T: Into<String> {
+        Variables::new(self, VariableScope::Project(id.into()))
+    }
+
+    pub fn group_variables<T>(&self, id: T) -> Variables
+        where T: Into<String> {
+        Variables::new(self, VariableScope::Group(id.into()))
+    }
+
+    pub fn instance_variables(&self) -> Variables {
+        Variables::new(self, VariableScope::Instance)
+    }
+
+    fn request_headers(&self) -> Result<Headers> {
         let mut h = Headers::with_capacity(5);
         h.set(ContentType(mime::APPLICATION_JSON));
         h.set(Accept(vec![qitem(mime::APPLICATION_JSON)]));
-        match self.credentials {
+        let credentials = self.current_credentials()?;
+        match credentials {
             Credentials::OauthToken(ref token) => {
                 h.set(Authorization(
                     Bearer {
@@ -96,43 +525,65 @@ impl Gitlab {
             Credentials::AccessToken(ref token) => {
                 h.set(GitlabPrivateToken(token.clone()))
             }
+            Credentials::JobToken(ref token) => {
+                h.set(GitlabJobToken(token.clone()))
+            }
+            Credentials::DeployToken { ref username, ref token } => {
+                h.set(Authorization(
+                    Basic {
+                        username: username.clone(),
+                        password: Some(token.clone()),
+                    }
+                ))
+            }
             _ => { ; }
         };
-        h
+        if let Some(ref user) = self.sudo {
+            if credentials == Credentials::Anonymous {
+                return Err(ErrorKind::SudoWithoutCredentials.into());
+            }
+            h.set(GitlabSudo(user.to_string()));
+        }
+        Ok(h)
     }
 
     fn get<T>(&self, resource: &str) -> Result<T>
         where T: DeserializeOwned,
     {
         let url = format!("{}{}", self.host, resource);
-        println!("URL: {}", url);
+        debug!("URL: {}", url);
 
         let mut rsp = self.http.get(&*url)?
-            .headers(self.request_headers())
+            .headers(self.request_headers()?)
             .send()?;
 
-        /*let mut content = String::new();
-        rsp.read_to_string(&mut content);
-        println!("{}", content);*/
-        match rsp.status() {
-            StatusCode::BadRequest |
-            StatusCode::Unauthorized |
-            StatusCode::Forbidden |
-            StatusCode::NotFound |
-            StatusCode::MethodNotAllowed |
-            StatusCode::Conflict |
-            StatusCode::UnprocessableEntity |
-            StatusCode::InternalServerError => {
-                Err(
-                    ErrorKind::Fault {
-                        // TODO Finalize JSON parsing for Error messages
-                        code: rsp.status(),
-                        error: String::from("Problem")
-                    }.into(),
-                )
-            }
-            _ => {
-                Ok(rsp.json()?)
+        Gitlab::deserialize_response(&mut rsp)
+    }
+
+    /// Fetch every page of a list resource, following `X-Next-Page`.
+    fn get_all<T>(&self, resource: &str) -> Result<Vec<T>>
+        where T: DeserializeOwned,
+    {
+        let separator = if resource.contains('?') { '&' } else { '?' };
+        let mut items = Vec::new();
+        let mut page = 1;
+
+        loop {
+            let url = format!("{}{}{}per_page={}&page={}", self.host, resource, separator, PER_PAGE, page);
+            debug!("URL: {}", url);
+
+            let mut rsp = self.http.get(&*url)?
+                .headers(self.request_headers()?)
+                .send()?;
+
+            let next_page = rsp.headers().get::<GitlabNextPage>()
+                .and_then(|next| next.0.parse::<u64>().ok());
+            let mut page_items: Vec<T> = Gitlab::deserialize_response(&mut rsp)?;
+            items.append(&mut page_items);
+
+            match next_page {
+                Some(next) => page = next,
+                None => return Ok(items),
             }
         }
     }
@@ -141,16 +592,115 @@ impl Gitlab {
         where T: DeserializeOwned,
     {
         let url = format!("{}{}", self.host, resource);
-        println!("Post URL: {}", url);
+        debug!("Post URL: {}", url);
+
+        let mut rsp = self.http.post(&*url)?
+            .headers(self.request_headers()?)
+            .body(body)
+            .send()?;
+
+        Gitlab::deserialize_response(&mut rsp)
+    }
+
+    /// POST a JSON body to an endpoint that answers with an empty body,
+    /// only checking the response status.
+    fn post_without_response(&self, resource: &str, body: Vec<u8>) -> Result<()> {
+        let url = format!("{}{}", self.host, resource);
+        debug!("Post URL: {}", url);
+
+        let rsp = self.http.post(&*url)?
+            .headers(self.request_headers()?)
+            .body(body)
+            .send()?;
+
+        Gitlab::check_status(&rsp)
+    }
+
+    /// POST to an endpoint that answers `304 Not Modified` with no body when
+    /// the request changes nothing, which is returned as `None`.
+    fn post_if_modified<T>(&self, resource: &str, body: Vec<u8>) -> Result<Option<T>>
+        where T: DeserializeOwned,
+    {
+        let url = format!("{}{}", self.host, resource);
+        debug!("Post URL: {}", url);
+
+        let mut rsp = self.http.post(&*url)?
+            .headers(self.request_headers()?)
+            .body(body)
+            .send()?;
+
+        if rsp.status() == StatusCode::NotModified {
+            return Ok(None);
+        }
+        Gitlab::deserialize_response(&mut rsp).map(Some)
+    }
+
+    /// POST a form-encoded body instead of JSON. A handful of endpoints,
+    /// such as pipeline triggers, only accept this encoding.
+    fn post_form<T>(&self, resource: &str, params: &[(String, String)]) -> Result<T>
+        where T: DeserializeOwned,
+    {
+        let url = format!("{}{}", self.host, resource);
+        debug!("Post URL: {}", url);
+
+        let body: String = form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(params)
+            .finish();
+
+        let mut headers = self.request_headers()?;
+        headers.set(ContentType::form_url_encoded());
 
         let mut rsp = self.http.post(&*url)?
-            .headers(self.request_headers())
+            .headers(headers)
             .body(body)
             .send()?;
 
-        /*let mut content = String::new();
-        rsp.read_to_string(&mut content);
-        println!("{}", content);*/
+        Gitlab::deserialize_response(&mut rsp)
+    }
+
+    fn put<T>(&self, resource: &str, body: Vec<u8>) -> Result<T>
+        where T: DeserializeOwned,
+    {
+        let url = format!("{}{}", self.host, resource);
+        debug!("Put URL: {}", url);
+
+        let mut rsp = self.http.put(&*url)?
+            .headers(self.request_headers()?)
+            .body(body)
+            .send()?;
+
+        Gitlab::deserialize_response(&mut rsp)
+    }
+
+    fn delete(&self, resource: &str) -> Result<()> {
+        let url = format!("{}{}", self.host, resource);
+        debug!("Delete URL: {}", url);
+
+        let rsp = self.http.delete(&*url)?
+            .headers(self.request_headers()?)
+            .send()?;
+
+        Gitlab::check_status(&rsp)
+    }
+
+    /// A DELETE carrying form parameters, for values that must stay out of
+    /// the URL and its logs.
+    fn delete_form(&self, resource: &str, params: &[(String, String)]) -> Result<()> {
+        let url = format!("{}{}", self.host, resource);
+        debug!("Delete URL: {}", url);
+
+        let mut headers = self.request_headers()?;
+        headers.set(ContentType::form_url_encoded());
+
+        let rsp = self.http.delete(&*url)?
+            .headers(headers)
+            .body(form_body(params))
+            .send()?;
+
+        Gitlab::check_status(&rsp)
+    }
+
+    fn check_status(rsp: &Response) -> Result<()> {
         match rsp.status() {
             StatusCode::BadRequest |
             StatusCode::Unauthorized |
@@ -168,30 +718,114 @@ impl Gitlab {
                     }.into(),
                 )
             }
-            _ => {
-                Ok(rsp.json()?)
-            }
+            _ => Ok(())
         }
     }
 
-    fn delete(&self, resource: &str) {
-        let url = format!("{}{}", self.host, resource);
-        println!("Delete URL: {}", url);
-
-        let mut rsp = self.http.delete(&*url).unwrap()
-            .headers(self.request_headers())
-            .send().unwrap();
-        
-        let mut content = String::new();
-        rsp.read_to_string(&mut content);
-        println!("{}", content);
+    fn deserialize_response<T>(rsp: &mut Response) -> Result<T>
+        where T: DeserializeOwned,
+    {
+        Gitlab::check_status(rsp)?;
+        Ok(rsp.json()?)
     }
 }
 
 
 #[cfg(test)]
 mod tests {
+    use super::{ci_host, Credentials, Gitlab, GitlabSudo, SudoUser};
+    use errors::ErrorKind;
+
+    use reqwest::Client;
+
+    use std::collections::HashMap;
+
+    fn client(credentials: Credentials) -> Gitlab {
+        Gitlab::new("https://gitlab.example.com", Client::new().unwrap(), credentials)
+    }
+
+    fn sudo_header(gitlab: &Gitlab) -> Option<String> {
+        gitlab.request_headers().unwrap().get::<GitlabSudo>().map(|h| h.0.clone())
+    }
+
     #[test]
     fn it_works() {
     }
+
+    #[test]
+    fn sudo_header_is_sent_when_set() {
+        let mut gitlab = client(Credentials::AccessToken(String::from("secret")));
+        assert_eq!(sudo_header(&gitlab), None);
+        gitlab.set_sudo(Some("alice")).unwrap();
+        assert_eq!(sudo_header(&gitlab), Some(String::from("alice")));
+        gitlab.set_sudo(Some(42)).unwrap();
+        assert_eq!(sudo_header(&gitlab), Some(String::from("42")));
+        gitlab.set_sudo(None::<SudoUser>).unwrap();
+        assert_eq!(sudo_header(&gitlab), None);
+    }
+
+    #[test]
+    fn sudo_needs_credentials() {
+        let mut gitlab = client(Credentials::Anonymous);
+        match *gitlab.set_sudo(Some("alice")).unwrap_err().kind() {
+            ErrorKind::SudoWithoutCredentials => (),
+            ref kind => panic!("unexpected error: {}", kind),
+        }
+        match gitlab.sudo("alice") {
+            Err(ref err) => match *err.kind() {
+                ErrorKind::SudoWithoutCredentials => (),
+                ref kind => panic!("unexpected error: {}", kind),
+            },
+            Ok(_) => panic!("sudo accepted anonymous credentials"),
+        }
+        assert_eq!(sudo_header(&gitlab), None);
+    }
+
+    #[test]
+    fn sudo_handle_leaves_parent_unchanged() {
+        let gitlab = client(Credentials::AccessToken(String::from("secret")));
+        let handle = gitlab.sudo("alice").unwrap();
+        assert_eq!(handle.host(), "https://gitlab.example.com");
+        assert_eq!(sudo_header(&handle), Some(String::from("alice")));
+        assert_eq!(handle.credentials, gitlab.credentials);
+        assert_eq!(sudo_header(&gitlab), None);
+        assert!(gitlab.sudo.is_none());
+    }
+
+    fn ci_env(vars: &[(&str, &str)]) -> Result<String, String> {
+        let vars: HashMap<String, String> = vars.iter()
+            .map(|&(k, v)| (k.to_owned(), v.to_owned()))
+            .collect();
+        ci_host(|name| vars.get(name).cloned()).map_err(|err| err.to_string())
+    }
+
+    #[test]
+    fn ci_host_resolution() {
+        assert_eq!(ci_env(&[("CI_SERVER_URL", "https://gitlab.example.com/")]),
+                   Ok(String::from("https://gitlab.example.com")));
+        assert_eq!(ci_env(&[("CI_SERVER_URL", "https://gitlab.example.com"),
+                            ("CI_API_V4_URL", "https://other.example.com/api/v4")]),
+                   Ok(String::from("https://gitlab.example.com")));
+        assert_eq!(ci_env(&[("CI_API_V4_URL", "https://gitlab.example.com/api/v4")]),
+                   Ok(String::from("https://gitlab.example.com")));
+        assert_eq!(ci_env(&[("CI_API_V4_URL", "https://example.com/gitlab/api/v4/")]),
+                   Ok(String::from("https://example.com/gitlab")));
+        let err = ci_env(&[]).unwrap_err();
+        assert!(err.contains("CI_SERVER_URL"));
+        assert!(err.contains("CI_API_V4_URL"));
+    }
+
+    #[test]
+    fn credentials_debug_is_redacted() {
+        let job = format!("{:?}", Credentials::JobToken(String::from("job-secret")));
+        assert_eq!(job, "JobToken([REDACTED])");
+        let deploy = Credentials::DeployToken {
+            username: String::from("deployer"),
+            token: String::from("deploy-secret"),
+        };
+        let deploy = format!("{:?}", deploy);
+        assert_eq!(deploy, "DeployToken { username: \"deployer\", token: [REDACTED] }");
+        let gitlab = format!("{:?}", client(Credentials::AccessToken(String::from("api-secret"))));
+        assert!(!gitlab.contains("api-secret"));
+    }
 }
@@ -0,0 +1,356 @@
+extern crate serde_json;
+
+use self::super::{Gitlab, Error, ErrorKind, Result, ProjectOrGroup};
+
+use serde::{Serialize, Serializer, Deserialize, Deserializer};
+use url::form_urlencoded;
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+/// A client for the [Members API](https://docs.gitlab.com/ee/api/members.html)
+/// of a project or group.
+pub struct Members<'a> {
+    gitlab: &'a Gitlab,
+    source: ProjectOrGroup,
+}
+
+/// Role of a member, sent to and received from GitLab as its numeric value.
+/// Levels are compared by their numeric value, so `Other(30)` is equal to
+/// `Developer`.
+#[derive(Debug, Clone, Copy)]
+pub enum AccessLevel {
+    NoAccess,
+    MinimalAccess,
+    Guest,
+    Planner,
+    Reporter,
+    Developer,
+    Maintainer,
+    Owner,
+    /// A level this crate does not know about yet.
+    Other(u8),
+}
+
+impl AccessLevel {
+    pub fn from_u8(level: u8) -> AccessLevel {
+        match level {
+            0 => AccessLevel::NoAccess,
+            5 => AccessLevel::MinimalAccess,
+            10 => AccessLevel::Guest,
+            15 => AccessLevel::Planner,
+            20 => AccessLevel::Reporter,
+            30 => AccessLevel::Developer,
+            40 => AccessLevel::Maintainer,
+            50 => AccessLevel::Owner,
+            level => AccessLevel::Other(level),
+        }
+    }
+
+    pub fn as_u8(&self) -> u8 {
+        match *self {
+            AccessLevel::NoAccess => 0,
+            AccessLevel::MinimalAccess => 5,
+            AccessLevel::Guest => 10,
+            AccessLevel::Planner => 15,
+            AccessLevel::Reporter => 20,
+            AccessLevel::Developer => 30,
+            AccessLevel::Maintainer => 40,
+            AccessLevel::Owner => 50,
+            AccessLevel::Other(level) => level,
+        }
+    }
+}
+
+impl PartialEq for AccessLevel {
+    fn eq(&self, other: &AccessLevel) -> bool {
+        self.as_u8() == other.as_u8()
+    }
+}
+
+impl Eq for AccessLevel {}
+
+impl Hash for AccessLevel {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_u8().hash(state);
+    }
+}
+
+impl PartialOrd for AccessLevel {
+    fn partial_cmp(&self, other: &AccessLevel) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for AccessLevel {
+    fn cmp(&self, other: &AccessLevel) -> Ordering {
+        self.as_u8().cmp(&other.as_u8())
+    }
+}
+
+impl From<u8> for AccessLevel {
+    fn from(level: u8) -> AccessLevel {
+        AccessLevel::from_u8(level)
+    }
+}
+
+impl From<AccessLevel> for u8 {
+    fn from(level: AccessLevel) -> u8 {
+        level.as_u8()
+    }
+}
+
+impl Serialize for AccessLevel {
+    fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        serializer.serialize_u8(self.as_u8())
+    }
+}
+
+impl<'de> Deserialize<'de> for AccessLevel {
+    fn deserialize<D>(deserializer: D) -> ::std::result::Result<AccessLevel, D::Error>
+        where D: Deserializer<'de>
+    {
+        u8::deserialize(deserializer).map(AccessLevel::from_u8)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    pub username: String,
+    pub name: String,
+    pub state: String,
+    pub avatar_url: Option<String>,
+    pub web_url: String,
+    pub access_level: AccessLevel,
+    pub expires_at: Option<String>,
+    pub created_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AccessRequest {
+    pub id: u64,
+    pub username: String,
+    pub name: String,
+    pub state: String,
+    pub created_at: Option<String>,
+    pub requested_at: Option<String>,
+}
+
+#[derive(Default)]
+pub struct MembersOptions {
+    pub params: HashMap<&'static str, String>,
+}
+
+impl MembersOptions {
+    pub fn builder() -> MembersOptionsBuilder {
+        MembersOptionsBuilder::new()
+    }
+
+    pub fn to_query_string(&self) -> Option<String> {
+        if self.params.is_empty() {
+            None
+        } else {
+            let encoded_qs: String = form_urlencoded::Serializer::new(String::new())
+                .extend_pairs(&self.params)
+                .finish();
+            Some(encoded_qs)
+        }
+    }
+}
+
+pub struct MembersOptionsBuilder(MembersOptions);
+
+impl MembersOptionsBuilder {
+    pub fn new() -> Self {
+        MembersOptionsBuilder(MembersOptions::default())
+    }
+
+    /// Filter members by name, email or username.
+    pub fn query<T>(&mut self, query: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.params.insert("query", query.into());
+        self
+    }
+
+    pub fn user_ids(&mut self, user_ids: &[u64]) -> &mut Self {
+        let ids: Vec<String> = user_ids.iter().map(|id| id.to_string()).collect();
+        self.0.params.insert("user_ids", ids.join(","));
+        self
+    }
+
+    pub fn build(&self) -> MembersOptions {
+        MembersOptions {
+            params: self.0.params.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MemberParams {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<u64>,
+    pub access_level: AccessLevel,
+    /// Date the membership expires, formatted `YYYY-MM-DD`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<String>,
+}
+
+impl MemberParams {
+    pub fn builder(user_id: u64, access_level: AccessLevel) -> MemberParamsBuilder {
+        MemberParamsBuilder::new(user_id, access_level)
+    }
+}
+
+pub struct MemberParamsBuilder(MemberParams);
+
+impl MemberParamsBuilder {
+    pub fn new(user_id: u64, access_level: AccessLevel) -> Self {
+        MemberParamsBuilder(MemberParams {
+            user_id: Some(user_id),
+            access_level: access_level,
+            expires_at: None,
+        })
+    }
+
+    pub fn expires_at<T>(&mut self, date: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.expires_at = Some(date.into());
+        self
+    }
+
+    pub fn build(&self) -> MemberParams {
+        self.0.clone()
+    }
+}
+
+#[derive(Serialize)]
+struct ApproveParams {
+    access_level: AccessLevel,
+}
+
+impl<'a> Members<'a> {
+    #[doc(hidden)]
+    pub fn new(gitlab: &'a Gitlab, source: ProjectOrGroup) -> Members<'a> {
+        Members {
+            gitlab: gitlab,
+            source: source,
+        }
+    }
+
+    fn resource(&self, more: &str) -> String {
+        self.source.resource(more)
+    }
+
+    fn list_resource(&self, base: String, options: &MembersOptions) -> Result<Vec<Member>> {
+        let mut uri = vec![base];
+        if let Some(query) = options.to_query_string() {
+            uri.push(query);
+        }
+        self.gitlab.get_all::<Member>(&uri.join("?"))
+    }
+
+    /// Direct members only.
+    pub fn list(&self, options: &MembersOptions) -> Result<Vec<Member>> {
+        self.list_resource(self.resource("/members"), options)
+    }
+
+    /// Direct members together with those inherited from ancestor groups.
+    pub fn list_all(&self, options: &MembersOptions) -> Result<Vec<Member>> {
+        self.list_resource(self.resource("/members/all"), options)
+    }
+
+    pub fn member(&self, user_id: u64) -> Result<Member> {
+        self.gitlab.get::<Member>(&self.resource(&format!("/members/{}", user_id)))
+    }
+
+    /// Look up a member, including memberships inherited from ancestor groups.
+    pub fn member_all(&self, user_id: u64) -> Result<Member> {
+        self.gitlab.get::<Member>(&self.resource(&format!("/members/all/{}", user_id)))
+    }
+
+    pub fn add(&self, params: &MemberParams) -> Result<Member> {
+        let json = serde_json::to_string(&params)?;
+        self.gitlab.post::<Member>(&self.resource("/members"), json.into_bytes())
+    }
+
+    pub fn edit(&self, user_id: u64, access_level: AccessLevel, expires_at: Option<String>) -> Result<Member> {
+        let params = MemberParams {
+            user_id: None,
+            access_level: access_level,
+            expires_at: expires_at,
+        };
+        let json = serde_json::to_string(&params)?;
+        self.gitlab.put::<Member>(&self.resource(&format!("/members/{}", user_id)), json.into_bytes())
+    }
+
+    pub fn remove(&self, user_id: u64) -> Result<()> {
+        self.gitlab.delete(&self.resource(&format!("/members/{}", user_id)))
+    }
+
+    pub fn access_requests(&self) -> Result<Vec<AccessRequest>> {
+        self.gitlab.get_all::<AccessRequest>(&self.resource("/access_requests"))
+    }
+
+    /// Request access for the authenticated user.
+    pub fn request_access(&self) -> Result<AccessRequest> {
+        self.gitlab.post::<AccessRequest>(&self.resource("/access_requests"), Vec::new())
+    }
+
+    pub fn approve_access_request(&self, user_id: u64, access_level: AccessLevel) -> Result<Member> {
+        let json = serde_json::to_string(&ApproveParams { access_level: access_level })?;
+        self.gitlab.put::<Member>(
+            &self.resource(&format!("/access_requests/{}/approve", user_id)),
+            json.into_bytes()
+        )
+    }
+
+    pub fn deny_access_request(&self, user_id: u64) -> Result<()> {
+        self.gitlab.delete(&self.resource(&format!("/access_requests/{}", user_id)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AccessLevel, MemberParams};
+    use serde_json;
+
+    use std::collections::HashSet;
+
+    #[test]
+    fn access_level_is_numeric() {
+        assert_eq!(serde_json::to_string(&AccessLevel::Maintainer).unwrap(), "40");
+        let level: AccessLevel = serde_json::from_str("30").unwrap();
+        assert_eq!(level, AccessLevel::Developer);
+        let level: AccessLevel = serde_json::from_str("15").unwrap();
+        assert_eq!(level, AccessLevel::Planner);
+        let level: AccessLevel = serde_json::from_str("31").unwrap();
+        assert_eq!(level, AccessLevel::Other(31));
+        assert!(AccessLevel::Other(31) > AccessLevel::Developer);
+        assert_eq!(serde_json::to_string(&level).unwrap(), "31");
+    }
+
+    #[test]
+    fn access_level_compares_by_value() {
+        let mut levels = HashSet::new();
+        levels.insert(AccessLevel::Developer);
+        assert_eq!(AccessLevel::Other(30), AccessLevel::Developer);
+        assert!(levels.contains(&AccessLevel::Other(30)));
+        assert!(AccessLevel::Other(40) >= AccessLevel::Maintainer);
+        assert!(AccessLevel::Other(31) != AccessLevel::Developer);
+    }
+
+    #[test]
+    fn params_are_serialized() {
+        let params = MemberParams::builder(7, AccessLevel::Reporter).build();
+        assert_eq!(serde_json::to_string(&params).unwrap(), r#"{"user_id":7,"access_level":20}"#);
+        let params = MemberParams::builder(7, AccessLevel::Reporter).expires_at("2026-12-31").build();
+        assert_eq!(serde_json::to_string(&params).unwrap(),
+                   r#"{"user_id":7,"access_level":20,"expires_at":"2026-12-31"}"#);
+    }
+}
@@ -0,0 +1,31 @@
+extern crate serde_json;
+
+use self::super::{Gitlab, Error, ErrorKind, Result};
+use projects::User;
+
+#[derive(Debug, Deserialize)]
+pub struct MergeRequest {
+    pub id: u64,
+    pub iid: u64,
+    pub project_id: u64,
+    pub title: String,
+    pub description: Option<String>,
+    pub state: String,
+    pub created_at: String,
+    pub updated_at: String,
+    pub merged_at: Option<String>,
+    pub closed_at: Option<String>,
+    pub source_branch: String,
+    pub target_branch: String,
+    pub source_project_id: Option<u64>,
+    pub target_project_id: Option<u64>,
+    pub author: Option<User>,
+    pub assignee: Option<User>,
+    #[serde(default)]
+    pub labels: Vec<String>,
+    pub draft: Option<bool>,
+    pub merge_status: Option<String>,
+    pub sha: Option<String>,
+    pub merge_commit_sha: Option<String>,
+    pub web_url: String,
+}
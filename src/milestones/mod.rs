@@ -0,0 +1,340 @@
+extern crate serde_json;
+
+use self::super::{Gitlab, Error, ErrorKind, Result, ProjectOrGroup};
+use issues::Issue;
+use merge_requests::MergeRequest;
+
+use chrono::{DateTime, NaiveDate, Utc};
+use url::form_urlencoded;
+
+use std::collections::HashMap;
+
+/// A client for the [Milestones API](https://docs.gitlab.com/ee/api/milestones.html)
+/// of a project or group.
+pub struct Milestones<'a> {
+    gitlab: &'a Gitlab,
+    source: ProjectOrGroup,
+}
+
+/// A client for the [Iterations API](https://docs.gitlab.com/ee/api/iterations.html)
+/// of a project or group.
+pub struct Iterations<'a> {
+    gitlab: &'a Gitlab,
+    source: ProjectOrGroup,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Milestone {
+    pub id: u64,
+    pub iid: u64,
+    pub project_id: Option<u64>,
+    pub group_id: Option<u64>,
+    pub title: String,
+    pub description: Option<String>,
+    /// `active` or `closed`
+    pub state: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub start_date: Option<NaiveDate>,
+    pub due_date: Option<NaiveDate>,
+    pub expired: Option<bool>,
+    pub web_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BurndownEvent {
+    pub created_at: DateTime<Utc>,
+    pub weight: Option<u64>,
+    /// `created`, `closed` or `reopened`
+    pub action: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Iteration {
+    pub id: u64,
+    pub iid: u64,
+    pub sequence: Option<u64>,
+    pub group_id: Option<u64>,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    /// 1 upcoming, 2 current, 3 closed
+    pub state: u8,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub start_date: Option<NaiveDate>,
+    pub due_date: Option<NaiveDate>,
+    pub web_url: Option<String>,
+}
+
+#[derive(Default)]
+pub struct MilestonesOptions {
+    pub params: HashMap<&'static str, String>,
+}
+
+impl MilestonesOptions {
+    pub fn builder() -> MilestonesOptionsBuilder {
+        MilestonesOptionsBuilder::new()
+    }
+
+    pub fn to_query_string(&self) -> Option<String> {
+        if self.params.is_empty() {
+            None
+        } else {
+            let encoded_qs: String = form_urlencoded::Serializer::new(String::new())
+                .extend_pairs(&self.params)
+                .finish();
+            Some(encoded_qs)
+        }
+    }
+}
+
+pub struct MilestonesOptionsBuilder(MilestonesOptions);
+
+impl MilestonesOptionsBuilder {
+    pub fn new() -> Self {
+        MilestonesOptionsBuilder(MilestonesOptions::default())
+    }
+
+    /// `active` or `closed`
+    pub fn state<T>(&mut self, state: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.params.insert("state", state.into());
+        self
+    }
+
+    /// Only the milestone with exactly this title.
+    pub fn title<T>(&mut self, title: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.params.insert("title", title.into());
+        self
+    }
+
+    pub fn search<T>(&mut self, search: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.params.insert("search", search.into());
+        self
+    }
+
+    /// Include milestones of ancestor groups.
+    pub fn include_ancestors(&mut self, include: bool) -> &mut Self {
+        self.0.params.insert("include_ancestors", include.to_string());
+        self
+    }
+
+    pub fn build(&self) -> MilestonesOptions {
+        MilestonesOptions {
+            params: self.0.params.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct MilestoneParams {
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_date: Option<NaiveDate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub due_date: Option<NaiveDate>,
+}
+
+impl MilestoneParams {
+    pub fn builder<T: Into<String>>(title: T) -> MilestoneParamsBuilder {
+        MilestoneParamsBuilder::new(title)
+    }
+}
+
+pub struct MilestoneParamsBuilder(MilestoneParams);
+
+impl MilestoneParamsBuilder {
+    pub fn new<T>(title: T) -> Self
+        where T: Into<String>
+    {
+        MilestoneParamsBuilder(MilestoneParams {
+            title: title.into(),
+            ..Default::default()
+        })
+    }
+
+    pub fn description<T>(&mut self, description: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.description = Some(description.into());
+        self
+    }
+
+    pub fn start_date(&mut self, date: NaiveDate) -> &mut Self {
+        self.0.start_date = Some(date);
+        self
+    }
+
+    pub fn due_date(&mut self, date: NaiveDate) -> &mut Self {
+        self.0.due_date = Some(date);
+        self
+    }
+
+    pub fn build(&self) -> MilestoneParams {
+        self.0.clone()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MilestoneStateEvent {
+    Close,
+    Activate,
+}
+
+/// Changes to an existing milestone; fields left as `None` are not touched.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct EditMilestoneParams {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_date: Option<NaiveDate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub due_date: Option<NaiveDate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state_event: Option<MilestoneStateEvent>,
+}
+
+impl<'a> Milestones<'a> {
+    #[doc(hidden)]
+    pub fn new(gitlab: &'a Gitlab, source: ProjectOrGroup) -> Milestones<'a> {
+        Milestones {
+            gitlab: gitlab,
+            source: source,
+        }
+    }
+
+    fn resource(&self, more: &str) -> String {
+        self.source.resource(&format!("/milestones{}", more))
+    }
+
+    pub fn list(&self, options: &MilestonesOptions) -> Result<Vec<Milestone>> {
+        let mut uri = vec![self.resource("")];
+        if let Some(query) = options.to_query_string() {
+            uri.push(query);
+        }
+        self.gitlab.get_all::<Milestone>(&uri.join("?"))
+    }
+
+    pub fn milestone(&self, milestone_id: u64) -> Result<Milestone> {
+        self.gitlab.get::<Milestone>(&self.resource(&format!("/{}", milestone_id)))
+    }
+
+    pub fn create(&self, params: &MilestoneParams) -> Result<Milestone> {
+        let json = serde_json::to_string(&params)?;
+        self.gitlab.post::<Milestone>(&self.resource(""), json.into_bytes())
+    }
+
+    pub fn edit(&self, milestone_id: u64, params: &EditMilestoneParams) -> Result<Milestone> {
+        let json = serde_json::to_string(&params)?;
+        self.gitlab.put::<Milestone>(&self.resource(&format!("/{}", milestone_id)), json.into_bytes())
+    }
+
+    pub fn close(&self, milestone_id: u64) -> Result<Milestone> {
+        let params = EditMilestoneParams {
+            state_event: Some(MilestoneStateEvent::Close),
+            ..Default::default()
+        };
+        self.edit(milestone_id, &params)
+    }
+
+    pub fn activate(&self, milestone_id: u64) -> Result<Milestone> {
+        let params = EditMilestoneParams {
+            state_event: Some(MilestoneStateEvent::Activate),
+            ..Default::default()
+        };
+        self.edit(milestone_id, &params)
+    }
+
+    pub fn delete(&self, milestone_id: u64) -> Result<()> {
+        self.gitlab.delete(&self.resource(&format!("/{}", milestone_id)))
+    }
+
+    pub fn issues(&self, milestone_id: u64) -> Result<Vec<Issue>> {
+        self.gitlab.get_all::<Issue>(&self.resource(&format!("/{}/issues", milestone_id)))
+    }
+
+    pub fn merge_requests(&self, milestone_id: u64) -> Result<Vec<MergeRequest>> {
+        self.gitlab.get_all::<MergeRequest>(&self.resource(&format!("/{}/merge_requests", milestone_id)))
+    }
+
+    pub fn burndown_events(&self, milestone_id: u64) -> Result<Vec<BurndownEvent>> {
+        self.gitlab.get_all::<BurndownEvent>(&self.resource(&format!("/{}/burndown_events", milestone_id)))
+    }
+
+    /// Turn a project milestone into a milestone of the project's group. Only
+    /// valid for project milestones.
+    pub fn promote(&self, milestone_id: u64) -> Result<Milestone> {
+        self.gitlab.post::<Milestone>(&self.resource(&format!("/{}/promote", milestone_id)), Vec::new())
+    }
+}
+
+impl<'a> Iterations<'a> {
+    #[doc(hidden)]
+    pub fn new(gitlab: &'a Gitlab, source: ProjectOrGroup) -> Iterations<'a> {
+        Iterations {
+            gitlab: gitlab,
+            source: source,
+        }
+    }
+
+    /// List iterations. `state` is one of `opened`, `upcoming`, `current`,
+    /// `closed` or `all`; `None` lists every iteration.
+    pub fn list(&self, state: Option<&str>) -> Result<Vec<Iteration>> {
+        let resource = self.source.resource("/iterations");
+        match state {
+            Some(state) => {
+                let query: String = form_urlencoded::Serializer::new(String::new())
+                    .append_pair("state", state)
+                    .finish();
+                self.gitlab.get_all::<Iteration>(&format!("{}?{}", resource, query))
+            }
+            None => self.gitlab.get_all::<Iteration>(&resource),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EditMilestoneParams, Milestone, MilestoneParams, MilestoneStateEvent};
+    use chrono::NaiveDate;
+    use serde_json;
+
+    #[test]
+    fn dates_are_typed() {
+        let params = MilestoneParams::builder("Sprint 4")
+            .start_date(NaiveDate::from_ymd(2026, 11, 2))
+            .due_date(NaiveDate::from_ymd(2026, 11, 13))
+            .build();
+        assert_eq!(serde_json::to_string(&params).unwrap(),
+                   r#"{"title":"Sprint 4","start_date":"2026-11-02","due_date":"2026-11-13"}"#);
+
+        let milestone: Milestone = serde_json::from_str(r#"{
+            "id": 12, "iid": 3, "project_id": 16, "title": "Sprint 4",
+            "description": null, "state": "active",
+            "created_at": "2026-10-01T09:30:00.000Z",
+            "updated_at": "2026-10-02T10:00:00.000Z",
+            "start_date": "2026-11-02", "due_date": null
+        }"#).unwrap();
+        assert_eq!(milestone.start_date, Some(NaiveDate::from_ymd(2026, 11, 2)));
+        assert_eq!(milestone.due_date, None);
+    }
+
+    #[test]
+    fn state_event_is_lowercase() {
+        let params = EditMilestoneParams {
+            state_event: Some(MilestoneStateEvent::Close),
+            ..Default::default()
+        };
+        assert_eq!(serde_json::to_string(&params).unwrap(), r#"{"state_event":"close"}"#);
+    }
+}
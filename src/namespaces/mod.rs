@@ -0,0 +1,149 @@
+extern crate serde_json;
+
+use self::super::{Gitlab, Error, ErrorKind, Result};
+use projects::Namespace;
+
+use url::percent_encoding::{utf8_percent_encode, PATH_SEGMENT_ENCODE_SET};
+use url::form_urlencoded;
+
+use std::collections::HashMap;
+
+/// A client for the [Namespaces API](https://docs.gitlab.com/ee/api/namespaces.html)
+pub struct Namespaces<'a> {
+    gitlab: &'a Gitlab,
+}
+
+/// Whether a namespace path is taken, with alternatives GitLab suggests if so.
+#[derive(Debug, Deserialize)]
+pub struct NamespaceExistence {
+    pub exists: bool,
+    #[serde(default)]
+    pub suggests: Vec<String>,
+}
+
+#[derive(Default)]
+pub struct NamespacesOptions {
+    pub params: HashMap<&'static str, String>,
+}
+
+impl NamespacesOptions {
+    pub fn builder() -> NamespacesOptionsBuilder {
+        NamespacesOptionsBuilder::new()
+    }
+
+    pub fn to_query_string(&self) -> Option<String> {
+        if self.params.is_empty() {
+            None
+        } else {
+            let encoded_qs: String = form_urlencoded::Serializer::new(String::new())
+                .extend_pairs(&self.params)
+                .finish();
+            Some(encoded_qs)
+        }
+    }
+}
+
+pub struct NamespacesOptionsBuilder(NamespacesOptions);
+
+impl NamespacesOptionsBuilder {
+    pub fn new() -> Self {
+        NamespacesOptionsBuilder(NamespacesOptions::default())
+    }
+
+    pub fn search<T>(&mut self, search: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.params.insert("search", search.into());
+        self
+    }
+
+    /// Only namespaces the user owns, rather than all they can see.
+    pub fn owned_only(&mut self, owned_only: bool) -> &mut Self {
+        self.0.params.insert("owned_only", owned_only.to_string());
+        self
+    }
+
+    pub fn build(&self) -> NamespacesOptions {
+        NamespacesOptions {
+            params: self.0.params.clone(),
+        }
+    }
+}
+
+fn namespace_resource(id: &str, more: &str) -> String {
+    let encoded_id = utf8_percent_encode(id, PATH_SEGMENT_ENCODE_SET).to_string();
+    format!("/namespaces/{}{}", encoded_id, more)
+}
+
+fn exists_resource(path: &str, parent_id: Option<u64>) -> String {
+    let mut uri = vec![namespace_resource(path, "/exists")];
+    if let Some(parent_id) = parent_id {
+        uri.push(form_urlencoded::Serializer::new(String::new())
+            .append_pair("parent_id", &parent_id.to_string())
+            .finish());
+    }
+    uri.join("?")
+}
+
+impl<'a> Namespaces<'a> {
+    #[doc(hidden)]
+    pub fn new(gitlab: &'a Gitlab) -> Namespaces<'a> {
+        Namespaces {
+            gitlab: gitlab,
+        }
+    }
+
+    pub fn list(&self, options: &NamespacesOptions) -> Result<Vec<Namespace>> {
+        let mut uri = vec![String::from("/namespaces")];
+        if let Some(query) = options.to_query_string() {
+            uri.push(query);
+        }
+        self.gitlab.get_all::<Namespace>(&uri.join("?"))
+    }
+
+    /// Get a namespace by ID or full path, e.g. `platform/tools`.
+    pub fn namespace<T>(&self, id: T) -> Result<Namespace>
+        where T: Into<String>
+    {
+        self.gitlab.get::<Namespace>(&namespace_resource(&id.into(), ""))
+    }
+
+    /// Resolve a full path to the ID expected by `ProjectParamsBuilder::namespace_id`.
+    pub fn id_for_path<T>(&self, path: T) -> Result<u64>
+        where T: Into<String>
+    {
+        self.namespace(path).map(|namespace| namespace.id)
+    }
+
+    /// Check whether `path` is taken, at the top level or under `parent_id`.
+    pub fn exists<T>(&self, path: T, parent_id: Option<u64>) -> Result<NamespaceExistence>
+        where T: Into<String>
+    {
+        self.gitlab.get::<NamespaceExistence>(&exists_resource(&path.into(), parent_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{exists_resource, namespace_resource, NamespaceExistence};
+    use serde_json;
+
+    #[test]
+    fn full_paths_are_encoded() {
+        assert_eq!(namespace_resource("platform/tools", ""), "/namespaces/platform%2Ftools");
+        assert_eq!(namespace_resource("42", ""), "/namespaces/42");
+    }
+
+    #[test]
+    fn exists_query() {
+        assert_eq!(exists_resource("tools", None), "/namespaces/tools/exists");
+        assert_eq!(exists_resource("tools", Some(12)), "/namespaces/tools/exists?parent_id=12");
+        let existence: NamespaceExistence = serde_json::from_str(
+            r#"{"exists": true, "suggests": ["tools1"]}"#
+        ).unwrap();
+        assert!(existence.exists);
+        assert_eq!(existence.suggests, vec!["tools1"]);
+        let existence: NamespaceExistence = serde_json::from_str(r#"{"exists": false}"#).unwrap();
+        assert!(existence.suggests.is_empty());
+    }
+}
@@ -0,0 +1,393 @@
+//! OAuth2 flows for obtaining `Credentials::OauthToken` from GitLab's
+//! [OAuth2 provider](https://docs.gitlab.com/ee/api/oauth2.html).
+
+use self::super::{Error, ErrorKind, Result};
+use secret::Secret;
+
+use base64;
+use rand::{self, Rng};
+use reqwest::Client;
+use reqwest::header::{ContentType, Accept, qitem};
+use reqwest::mime;
+use serde_json;
+use sha2::{Sha256, Digest};
+use url::Url;
+use url::form_urlencoded;
+
+use std::io::{Read, Write, BufRead, BufReader};
+use std::net::TcpListener;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Seconds before expiry at which a token is treated as expired, so that it
+/// is not used for a request that reaches GitLab after it has lapsed.
+const EXPIRY_MARGIN: u64 = 60;
+
+/// Seconds to wait for a connection to the loopback redirect to send its
+/// request before moving on to the next one.
+const REDIRECT_READ_TIMEOUT: u64 = 5;
+
+/// A token issued by `/oauth/token`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct OauthToken {
+    pub access_token: Secret,
+    pub token_type: String,
+    pub expires_in: Option<u64>,
+    pub refresh_token: Option<Secret>,
+    pub scope: Option<String>,
+    /// Unix timestamp at which the token was issued.
+    pub created_at: Option<u64>,
+}
+
+impl OauthToken {
+    /// Whether the token has expired or is about to. Tokens without an
+    /// expiry never expire.
+    pub fn is_expired(&self) -> bool {
+        match (self.created_at, self.expires_in) {
+            (Some(created_at), Some(expires_in)) => {
+                let now = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_secs())
+                    .unwrap_or(0);
+                now + EXPIRY_MARGIN >= created_at + expires_in
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Response to starting the device authorization grant. Show the user
+/// `user_code` and `verification_uri`, then call `OauthClient::poll_device`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct DeviceAuthorization {
+    pub device_code: Secret,
+    pub user_code: String,
+    pub verification_uri: String,
+    pub verification_uri_complete: Option<String>,
+    pub expires_in: u64,
+    #[serde(default = "default_interval")]
+    pub interval: u64,
+}
+
+fn default_interval() -> u64 {
+    5
+}
+
+#[derive(Debug, Deserialize)]
+struct OauthErrorResponse {
+    error: String,
+    error_description: Option<String>,
+}
+
+/// A PKCE code verifier and the matching `S256` challenge.
+#[derive(Debug, Clone)]
+pub struct Pkce {
+    pub verifier: Secret,
+    pub challenge: String,
+}
+
+impl Pkce {
+    pub fn new() -> Pkce {
+        let verifier: String = rand::thread_rng().gen_ascii_chars().take(64).collect();
+        Pkce::from_verifier(verifier)
+    }
+
+    fn from_verifier(verifier: String) -> Pkce {
+        let challenge = base64::encode_config(&Sha256::digest(verifier.as_bytes()), base64::URL_SAFE_NO_PAD);
+        Pkce {
+            verifier: Secret::new(verifier),
+            challenge: challenge,
+        }
+    }
+}
+
+/// Random value for the `state` parameter of an authorization request.
+pub fn random_state() -> String {
+    rand::thread_rng().gen_ascii_chars().take(32).collect()
+}
+
+/// An OAuth2 application registered with a GitLab instance.
+#[derive(Debug, Clone)]
+pub struct OauthClient {
+    host: String,
+    http: Client,
+    client_id: String,
+    client_secret: Option<Secret>,
+    redirect_uri: Option<String>,
+}
+
+impl OauthClient {
+    /// Create a client for a public application. `host` is the instance root,
+    /// e.g. `https://gitlab.com`.
+    pub fn new<T, U>(host: T, http: Client, client_id: U) -> OauthClient
+        where T: Into<String>,
+              U: Into<String>,
+    {
+        OauthClient {
+            host: host.into(),
+            http: http,
+            client_id: client_id.into(),
+            client_secret: None,
+            redirect_uri: None,
+        }
+    }
+
+    /// Secret for confidential applications.
+    pub fn client_secret<T>(mut self, secret: T) -> Self
+        where T: Into<Secret>
+    {
+        self.client_secret = Some(secret.into());
+        self
+    }
+
+    pub fn redirect_uri<T>(mut self, redirect_uri: T) -> Self
+        where T: Into<String>
+    {
+        self.redirect_uri = Some(redirect_uri.into());
+        self
+    }
+
+    /// URL to open in the user's browser to start the authorization code flow.
+    pub fn authorize_url(&self, state: &str, pkce: &Pkce, scopes: &[&str]) -> Result<String> {
+        let mut url = Url::parse(&format!("{}/oauth/authorize", self.host))
+            .map_err(|e| Error::from(ErrorKind::Oauth(String::from("invalid_request"), e.to_string())))?;
+        url.query_pairs_mut()
+            .append_pair("client_id", &self.client_id)
+            .append_pair("redirect_uri", self.redirect_uri.as_ref().map(|s| s.as_str()).unwrap_or(""))
+            .append_pair("response_type", "code")
+            .append_pair("state", state)
+            .append_pair("scope", &scopes.join(" "))
+            .append_pair("code_challenge", &pkce.challenge)
+            .append_pair("code_challenge_method", "S256");
+        Ok(url.into_string())
+    }
+
+    /// Exchange an authorization code for a token.
+    pub fn exchange_code(&self, code: &str, pkce: &Pkce) -> Result<OauthToken> {
+        let mut form = self.client_params("authorization_code");
+        form.push((String::from("code"), code.to_owned()));
+        form.push((String::from("code_verifier"), pkce.verifier.expose().to_owned()));
+        self.token_request(&form)
+    }
+
+    /// Resource owner password credentials grant.
+    pub fn password(&self, username: &str, password: &Secret, scopes: &[&str]) -> Result<OauthToken> {
+        let mut form = self.client_params("password");
+        form.push((String::from("username"), username.to_owned()));
+        form.push((String::from("password"), password.expose().to_owned()));
+        form.push((String::from("scope"), scopes.join(" ")));
+        self.token_request(&form)
+    }
+
+    pub fn refresh(&self, refresh_token: &Secret) -> Result<OauthToken> {
+        let mut form = self.client_params("refresh_token");
+        form.push((String::from("refresh_token"), refresh_token.expose().to_owned()));
+        self.token_request(&form)
+    }
+
+    /// Start the device authorization grant.
+    pub fn authorize_device(&self, scopes: &[&str]) -> Result<DeviceAuthorization> {
+        let form = vec![
+            (String::from("client_id"), self.client_id.clone()),
+            (String::from("scope"), scopes.join(" ")),
+        ];
+        let body = self.form_request("/oauth/authorize_device", &form)?;
+        Ok(serde_json::from_str(&body)?)
+    }
+
+    /// Poll until the user approves or denies a device authorization, or
+    /// the device code expires.
+    pub fn poll_device(&self, device: &DeviceAuthorization) -> Result<OauthToken> {
+        let form = vec![
+            (String::from("grant_type"), String::from("urn:ietf:params:oauth:grant-type:device_code")),
+            (String::from("client_id"), self.client_id.clone()),
+            (String::from("device_code"), device.device_code.expose().to_owned()),
+        ];
+        let mut interval = device.interval;
+        loop {
+            thread::sleep(Duration::from_secs(interval));
+            match self.token_request(&form) {
+                Err(Error(ErrorKind::Oauth(ref error, _), _)) if error == "authorization_pending" => {}
+                Err(Error(ErrorKind::Oauth(ref error, _), _)) if error == "slow_down" => {
+                    interval += 5;
+                }
+                other => return other,
+            }
+        }
+    }
+
+    fn client_params(&self, grant_type: &str) -> Vec<(String, String)> {
+        let mut form = vec![
+            (String::from("grant_type"), grant_type.to_owned()),
+            (String::from("client_id"), self.client_id.clone()),
+        ];
+        if let Some(ref secret) = self.client_secret {
+            form.push((String::from("client_secret"), secret.expose().to_owned()));
+        }
+        if let Some(ref redirect_uri) = self.redirect_uri {
+            form.push((String::from("redirect_uri"), redirect_uri.clone()));
+        }
+        form
+    }
+
+    fn token_request(&self, form: &[(String, String)]) -> Result<OauthToken> {
+        let body = self.form_request("/oauth/token", form)?;
+        Ok(serde_json::from_str(&body)?)
+    }
+
+    /// POST a form to the OAuth provider. Errors are reported in the body as
+    /// `error` and `error_description` rather than through the API's usual
+    /// error format, so the response is handled here instead of by `Gitlab`.
+    fn form_request(&self, path: &str, form: &[(String, String)]) -> Result<String> {
+        let url = format!("{}{}", self.host, path);
+        let body: String = form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(form)
+            .finish();
+
+        let mut rsp = self.http.post(&*url)?
+            .header(ContentType::form_url_encoded())
+            .header(Accept(vec![qitem(mime::APPLICATION_JSON)]))
+            .body(body)
+            .send()?;
+
+        let mut content = String::new();
+        rsp.read_to_string(&mut content)?;
+
+        if rsp.status().is_success() {
+            Ok(content)
+        } else {
+            match serde_json::from_str::<OauthErrorResponse>(&content) {
+                Ok(e) => Err(ErrorKind::Oauth(e.error, e.error_description.unwrap_or_default()).into()),
+                Err(_) => Err(ErrorKind::Fault { code: rsp.status(), error: content }.into()),
+            }
+        }
+    }
+}
+
+/// A one-shot HTTP listener on the loopback interface that receives the
+/// authorization code redirect for native applications such as the CLI.
+pub struct LoopbackRedirect {
+    listener: TcpListener,
+    redirect_uri: String,
+}
+
+impl LoopbackRedirect {
+    /// Listen on an ephemeral port. Pass `redirect_uri()` to the
+    /// `OauthClient` and register it with the application.
+    pub fn bind() -> Result<LoopbackRedirect> {
+        LoopbackRedirect::bind_port(0)
+    }
+
+    pub fn bind_port(port: u16) -> Result<LoopbackRedirect> {
+        let listener = TcpListener::bind(("127.0.0.1", port))?;
+        let port = listener.local_addr()?.port();
+        Ok(LoopbackRedirect {
+            listener: listener,
+            redirect_uri: format!("http://127.0.0.1:{}/callback", port),
+        })
+    }
+
+    pub fn redirect_uri(&self) -> &str {
+        &self.redirect_uri
+    }
+
+    /// Wait for the browser to be redirected back and return the
+    /// authorization code, checking that `state` matches. Other requests,
+    /// such as for `/favicon.ico` or idle pre-connections, are skipped.
+    pub fn wait_for_code(&self, state: &str) -> Result<String> {
+        loop {
+            let (mut stream, _) = self.listener.accept()?;
+            stream.set_read_timeout(Some(Duration::from_secs(REDIRECT_READ_TIMEOUT)))?;
+
+            let mut request_line = String::new();
+            if BufReader::new(&stream).read_line(&mut request_line).is_err() {
+                continue;
+            }
+            let target = request_line.split_whitespace().nth(1).unwrap_or("/").to_owned();
+            if !is_redirect(&target) {
+                let _ = write!(stream, "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n");
+                continue;
+            }
+
+            let result = parse_redirect(&target, state);
+            let page = match result {
+                Ok(_) => "Authorization complete. You can close this window.",
+                Err(_) => "Authorization failed. Return to the application for details.",
+            };
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                page.len(),
+                page
+            )?;
+            return result;
+        }
+    }
+}
+
+/// Whether a request target is GitLab's redirect rather than something else
+/// the browser asked for.
+fn is_redirect(target: &str) -> bool {
+    Url::parse(&format!("http://127.0.0.1{}", target))
+        .map(|url| url.query_pairs().any(|(key, _)| key == "code" || key == "state" || key == "error"))
+        .unwrap_or(false)
+}
+
+fn parse_redirect(target: &str, state: &str) -> Result<String> {
+    let url = Url::parse(&format!("http://127.0.0.1{}", target))
+        .map_err(|e| Error::from(ErrorKind::Oauth(String::from("invalid_request"), e.to_string())))?;
+
+    let mut code = None;
+    let mut returned_state = None;
+    let mut error = None;
+    let mut description = String::new();
+    for (key, value) in url.query_pairs() {
+        match &*key {
+            "code" => code = Some(value.into_owned()),
+            "state" => returned_state = Some(value.into_owned()),
+            "error" => error = Some(value.into_owned()),
+            "error_description" => description = value.into_owned(),
+            _ => {}
+        }
+    }
+
+    if let Some(error) = error {
+        return Err(ErrorKind::Oauth(error, description).into());
+    }
+    if returned_state.as_ref().map(|s| s.as_str()) != Some(state) {
+        return Err(ErrorKind::Oauth(
+            String::from("invalid_state"),
+            String::from("state returned by the redirect does not match the request"),
+        ).into());
+    }
+    code.ok_or_else(|| ErrorKind::Oauth(
+        String::from("invalid_request"),
+        String::from("redirect did not include an authorization code"),
+    ).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Pkce, is_redirect, parse_redirect};
+
+    #[test]
+    fn pkce_challenge_matches_rfc7636_example() {
+        let pkce = Pkce::from_verifier(String::from("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk"));
+        assert_eq!(pkce.challenge, "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM");
+    }
+
+    #[test]
+    fn redirect_state_is_checked() {
+        assert_eq!(parse_redirect("/callback?code=abc&state=xyz", "xyz").unwrap(), "abc");
+        assert!(parse_redirect("/callback?code=abc&state=other", "xyz").is_err());
+        assert!(parse_redirect("/callback?error=access_denied&state=xyz", "xyz").is_err());
+    }
+
+    #[test]
+    fn unrelated_requests_are_not_redirects() {
+        assert!(is_redirect("/callback?code=abc&state=xyz"));
+        assert!(is_redirect("/callback?error=access_denied"));
+        assert!(!is_redirect("/favicon.ico"));
+        assert!(!is_redirect("/"));
+    }
+}
@@ -0,0 +1,461 @@
+extern crate serde_json;
+
+use self::super::{Gitlab, Error, ErrorKind, Result};
+use projects::User;
+use secret::Secret;
+
+use url::percent_encoding::{utf8_percent_encode, PATH_SEGMENT_ENCODE_SET};
+use url::form_urlencoded;
+
+use std::collections::HashMap;
+
+/// A client for the [Pipeline schedules API](https://docs.gitlab.com/ee/api/pipeline_schedules.html)
+pub struct PipelineSchedules<'a> {
+    gitlab: &'a Gitlab,
+}
+
+/// A client for the [Pipeline triggers API](https://docs.gitlab.com/ee/api/pipeline_triggers.html)
+pub struct PipelineTriggers<'a> {
+    gitlab: &'a Gitlab,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Pipeline {
+    pub id: u64,
+    pub sha: String,
+    #[serde(rename = "ref")]
+    pub ref_name: String,
+    pub status: String,
+    pub web_url: Option<String>,
+    pub created_at: Option<String>,
+    pub updated_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PipelineVariable {
+    pub key: String,
+    pub value: String,
+    pub variable_type: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PipelineSchedule {
+    pub id: u64,
+    pub description: String,
+    #[serde(rename = "ref")]
+    pub ref_name: String,
+    pub cron: String,
+    pub cron_timezone: String,
+    pub next_run_at: Option<String>,
+    pub active: bool,
+    pub created_at: String,
+    pub updated_at: String,
+    pub owner: Option<User>,
+    pub last_pipeline: Option<Pipeline>,
+    #[serde(default)]
+    pub variables: Vec<PipelineVariable>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PipelineTrigger {
+    pub id: u64,
+    pub description: Option<String>,
+    pub token: Secret,
+    pub created_at: String,
+    pub updated_at: String,
+    pub last_used: Option<String>,
+    pub owner: Option<User>,
+}
+
+#[derive(Default)]
+pub struct PipelineSchedulesOptions {
+    pub id: String,
+    pub params: HashMap<&'static str, String>,
+}
+
+impl PipelineSchedulesOptions {
+    pub fn builder<T: Into<String>>(id: T) -> PipelineSchedulesOptionsBuilder {
+        PipelineSchedulesOptionsBuilder::new(id)
+    }
+
+    pub fn to_query_string(&self) -> Option<String> {
+        if self.params.is_empty() {
+            None
+        } else {
+            let encoded_qs: String = form_urlencoded::Serializer::new(String::new())
+                .extend_pairs(&self.params)
+                .finish();
+            Some(encoded_qs)
+        }
+    }
+}
+
+pub struct PipelineSchedulesOptionsBuilder(PipelineSchedulesOptions);
+
+impl PipelineSchedulesOptionsBuilder {
+    pub fn new<T>(id: T) -> Self
+        where T: Into<String>,
+    {
+        PipelineSchedulesOptionsBuilder(PipelineSchedulesOptions {
+            id: id.into(),
+            ..Default::default()
+        })
+    }
+
+    /// Only return `active` or `inactive` schedules.
+    pub fn scope<T>(&mut self, scope: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.params.insert("scope", scope.into());
+        self
+    }
+
+    pub fn build(&self) -> PipelineSchedulesOptions {
+        PipelineSchedulesOptions {
+            id: self.0.id.clone(),
+            params: self.0.params.clone(),
+        }
+    }
+}
+
+/// Parameters for creating or editing a pipeline schedule.
+///
+/// `description`, `ref` and `cron` are required when creating a schedule;
+/// when editing, only the fields that are set are changed.
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct PipelineScheduleParams {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(rename = "ref", skip_serializing_if = "Option::is_none")]
+    pub ref_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cron: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cron_timezone: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub active: Option<bool>,
+}
+
+impl PipelineScheduleParams {
+    pub fn builder() -> PipelineScheduleParamsBuilder {
+        PipelineScheduleParamsBuilder::new()
+    }
+}
+
+pub struct PipelineScheduleParamsBuilder(PipelineScheduleParams);
+
+impl PipelineScheduleParamsBuilder {
+    pub fn new() -> Self {
+        PipelineScheduleParamsBuilder(PipelineScheduleParams::default())
+    }
+
+    pub fn description<T>(&mut self, description: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.description = Some(description.into());
+        self
+    }
+
+    pub fn ref_name<T>(&mut self, ref_name: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.ref_name = Some(ref_name.into());
+        self
+    }
+
+    pub fn cron<T>(&mut self, cron: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.cron = Some(cron.into());
+        self
+    }
+
+    /// Timezone supported by `ActiveSupport::TimeZone`, e.g. `Europe/Berlin`.
+    pub fn cron_timezone<T>(&mut self, timezone: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.cron_timezone = Some(timezone.into());
+        self
+    }
+
+    pub fn active(&mut self, active: bool) -> &mut Self {
+        self.0.active = Some(active);
+        self
+    }
+
+    pub fn build(&self) -> PipelineScheduleParams {
+        self.0.clone()
+    }
+}
+
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct PipelineVariableParams {
+    pub key: String,
+    pub value: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub variable_type: Option<String>,
+}
+
+impl PipelineVariableParams {
+    pub fn new<T, U>(key: T, value: U) -> Self
+        where T: Into<String>,
+              U: Into<String>,
+    {
+        PipelineVariableParams {
+            key: key.into(),
+            value: value.into(),
+            variable_type: None,
+        }
+    }
+
+    /// Either `env_var` (the default) or `file`.
+    pub fn variable_type<T>(mut self, variable_type: T) -> Self
+        where T: Into<String>
+    {
+        self.variable_type = Some(variable_type.into());
+        self
+    }
+}
+
+/// Changes to an existing schedule variable, which is named separately.
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct EditPipelineVariableParams {
+    pub value: String,
+    /// Either `env_var` or `file`; left unchanged if `None`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub variable_type: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize)]
+struct TriggerDescription {
+    description: String,
+}
+
+/// Parameters for triggering a pipeline with a trigger token.
+#[derive(Debug, Default, Clone)]
+pub struct TriggerPipelineParams {
+    pub token: Secret,
+    pub ref_name: String,
+    pub variables: HashMap<String, String>,
+}
+
+impl TriggerPipelineParams {
+    pub fn new<T, U>(token: T, ref_name: U) -> Self
+        where T: Into<Secret>,
+              U: Into<String>,
+    {
+        TriggerPipelineParams {
+            token: token.into(),
+            ref_name: ref_name.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn variable<T, U>(mut self, key: T, value: U) -> Self
+        where T: Into<String>,
+              U: Into<String>,
+    {
+        self.variables.insert(key.into(), value.into());
+        self
+    }
+
+    /// Form-encoded pairs, with variables sent as `variables[KEY]=value`.
+    fn to_form(&self) -> Vec<(String, String)> {
+        let mut form = vec![
+            (String::from("token"), self.token.expose().to_owned()),
+            (String::from("ref"), self.ref_name.clone()),
+        ];
+        for (key, value) in &self.variables {
+            form.push((format!("variables[{}]", key), value.clone()));
+        }
+        form
+    }
+}
+
+fn project_resource(id: &str, more: &str) -> String {
+    let encoded_id = utf8_percent_encode(id, PATH_SEGMENT_ENCODE_SET).to_string();
+    format!("/projects/{}{}", encoded_id, more)
+}
+
+impl<'a> PipelineSchedules<'a> {
+    #[doc(hidden)]
+    pub fn new(gitlab: &'a Gitlab) -> PipelineSchedules<'a> {
+        PipelineSchedules {
+            gitlab: gitlab,
+        }
+    }
+
+    fn resource(&self, id: &str, more: &str) -> String {
+        project_resource(id, &format!("/pipeline_schedules{}", more))
+    }
+
+    pub fn list(&self, options: &PipelineSchedulesOptions) -> Result<Vec<PipelineSchedule>> {
+        let mut uri = vec![self.resource(&options.id, "")];
+        if let Some(query) = options.to_query_string() {
+            uri.push(query);
+        }
+        self.gitlab.get_all::<PipelineSchedule>(&uri.join("?"))
+    }
+
+    pub fn schedule<T>(&self, id: T, schedule_id: u64) -> Result<PipelineSchedule>
+        where T: Into<String>
+    {
+        self.gitlab.get::<PipelineSchedule>(
+            &self.resource(&id.into(), &format!("/{}", schedule_id))
+        )
+    }
+
+    pub fn create<T>(&self, id: T, params: &PipelineScheduleParams) -> Result<PipelineSchedule>
+        where T: Into<String>
+    {
+        let json = serde_json::to_string(&params)?;
+        self.gitlab.post::<PipelineSchedule>(&self.resource(&id.into(), ""), json.into_bytes())
+    }
+
+    pub fn edit<T>(&self, id: T, schedule_id: u64, params: &PipelineScheduleParams) -> Result<PipelineSchedule>
+        where T: Into<String>
+    {
+        let json = serde_json::to_string(&params)?;
+        self.gitlab.put::<PipelineSchedule>(
+            &self.resource(&id.into(), &format!("/{}", schedule_id)),
+            json.into_bytes()
+        )
+    }
+
+    pub fn delete<T>(&self, id: T, schedule_id: u64) -> Result<()>
+        where T: Into<String>
+    {
+        self.gitlab.delete(&self.resource(&id.into(), &format!("/{}", schedule_id)))
+    }
+
+    /// Make the authenticated user the owner of a schedule.
+    pub fn take_ownership<T>(&self, id: T, schedule_id: u64) -> Result<PipelineSchedule>
+        where T: Into<String>
+    {
+        self.gitlab.post::<PipelineSchedule>(
+            &self.resource(&id.into(), &format!("/{}/take_ownership", schedule_id)),
+            Vec::new()
+        )
+    }
+
+    /// Run a schedule immediately. The pipeline is queued asynchronously.
+    pub fn play<T>(&self, id: T, schedule_id: u64) -> Result<()>
+        where T: Into<String>
+    {
+        self.gitlab.post::<serde_json::Value>(
+            &self.resource(&id.into(), &format!("/{}/play", schedule_id)),
+            Vec::new()
+        )?;
+        Ok(())
+    }
+
+    pub fn create_variable<T>(&self, id: T, schedule_id: u64, params: &PipelineVariableParams) -> Result<PipelineVariable>
+        where T: Into<String>
+    {
+        let json = serde_json::to_string(&params)?;
+        self.gitlab.post::<PipelineVariable>(
+            &self.resource(&id.into(), &format!("/{}/variables", schedule_id)),
+            json.into_bytes()
+        )
+    }
+
+    pub fn edit_variable<T, U>(&self, id: T, schedule_id: u64, key: U, params: &EditPipelineVariableParams) -> Result<PipelineVariable>
+        where T: Into<String>,
+              U: Into<String>,
+    {
+        let key = utf8_percent_encode(&key.into(), PATH_SEGMENT_ENCODE_SET).to_string();
+        let json = serde_json::to_string(&params)?;
+        self.gitlab.put::<PipelineVariable>(
+            &self.resource(&id.into(), &format!("/{}/variables/{}", schedule_id, key)),
+            json.into_bytes()
+        )
+    }
+
+    pub fn delete_variable<T, U>(&self, id: T, schedule_id: u64, key: U) -> Result<()>
+        where T: Into<String>,
+              U: Into<String>,
+    {
+        let key = utf8_percent_encode(&key.into(), PATH_SEGMENT_ENCODE_SET).to_string();
+        self.gitlab.delete(
+            &self.resource(&id.into(), &format!("/{}/variables/{}", schedule_id, key))
+        )
+    }
+}
+
+impl<'a> PipelineTriggers<'a> {
+    #[doc(hidden)]
+    pub fn new(gitlab: &'a Gitlab) -> PipelineTriggers<'a> {
+        PipelineTriggers {
+            gitlab: gitlab,
+        }
+    }
+
+    fn resource(&self, id: &str, more: &str) -> String {
+        project_resource(id, &format!("/triggers{}", more))
+    }
+
+    pub fn list<T>(&self, id: T) -> Result<Vec<PipelineTrigger>>
+        where T: Into<String>
+    {
+        self.gitlab.get_all::<PipelineTrigger>(&self.resource(&id.into(), ""))
+    }
+
+    pub fn trigger_token<T>(&self, id: T, trigger_id: u64) -> Result<PipelineTrigger>
+        where T: Into<String>
+    {
+        self.gitlab.get::<PipelineTrigger>(
+            &self.resource(&id.into(), &format!("/{}", trigger_id))
+        )
+    }
+
+    pub fn create<T, U>(&self, id: T, description: U) -> Result<PipelineTrigger>
+        where T: Into<String>,
+              U: Into<String>,
+    {
+        let json = serde_json::to_string(&TriggerDescription { description: description.into() })?;
+        self.gitlab.post::<PipelineTrigger>(&self.resource(&id.into(), ""), json.into_bytes())
+    }
+
+    pub fn edit<T, U>(&self, id: T, trigger_id: u64, description: U) -> Result<PipelineTrigger>
+        where T: Into<String>,
+              U: Into<String>,
+    {
+        let json = serde_json::to_string(&TriggerDescription { description: description.into() })?;
+        self.gitlab.put::<PipelineTrigger>(
+            &self.resource(&id.into(), &format!("/{}", trigger_id)),
+            json.into_bytes()
+        )
+    }
+
+    pub fn delete<T>(&self, id: T, trigger_id: u64) -> Result<()>
+        where T: Into<String>
+    {
+        self.gitlab.delete(&self.resource(&id.into(), &format!("/{}", trigger_id)))
+    }
+
+    /// Trigger a pipeline on `ref` using a trigger token. This endpoint only
+    /// accepts a form-encoded body, so it does not go through the JSON post.
+    pub fn trigger<T>(&self, id: T, params: &TriggerPipelineParams) -> Result<Pipeline>
+        where T: Into<String>
+    {
+        self.gitlab.post_form::<Pipeline>(
+            &project_resource(&id.into(), "/trigger/pipeline"),
+            &params.to_form()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TriggerPipelineParams;
+
+    #[test]
+    fn trigger_variables_are_bracketed() {
+        let params = TriggerPipelineParams::new("abc123", "main")
+            .variable("DEPLOY_ENV", "staging");
+        let form = params.to_form();
+        assert_eq!(form[0], (String::from("token"), String::from("abc123")));
+        assert_eq!(form[1], (String::from("ref"), String::from("main")));
+        assert_eq!(form[2], (String::from("variables[DEPLOY_ENV]"), String::from("staging")));
+    }
+}
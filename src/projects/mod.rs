@@ -1,6 +1,8 @@
 extern crate serde_json;
 
 use self::super::{Gitlab, Error, ErrorKind, Result};
+use members::AccessLevel;
+use secret::Secret;
 
 use url::Url;
 use url::percent_encoding::{utf8_percent_encode, PATH_SEGMENT_ENCODE_SET};
@@ -32,17 +34,45 @@ pub struct Permission {
     pub notification_level: u8,
 }
 
+impl Permission {
+    /// The typed role for `access_level`.
+    pub fn level(&self) -> AccessLevel {
+        AccessLevel::from_u8(self.access_level)
+    }
+}
+
 #[derive(Debug, Deserialize)]
 pub struct PermissionsWrapper {
     pub project_access: Option<Permission>,
     pub group_access: Option<Permission>,
 }
 
-#[derive(Debug, Deserialize)]
+/// A GitLab user. Only `id` and `name` are always present; how many of the
+/// remaining fields are filled in depends on the endpoint and on whether the
+/// caller is an administrator.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct User {
     pub id: u64,
     pub name: String,
-    //pub created_at: String,
+    pub username: Option<String>,
+    pub state: Option<String>,
+    pub avatar_url: Option<String>,
+    pub web_url: Option<String>,
+    pub created_at: Option<String>,
+    pub bio: Option<String>,
+    pub location: Option<String>,
+    pub public_email: Option<String>,
+    pub email: Option<String>,
+    pub organization: Option<String>,
+    pub job_title: Option<String>,
+    pub bot: Option<bool>,
+    pub is_admin: Option<bool>,
+    pub external: Option<bool>,
+    pub two_factor_enabled: Option<bool>,
+    pub confirmed_at: Option<String>,
+    pub last_sign_in_at: Option<String>,
+    pub current_sign_in_at: Option<String>,
+    pub last_activity_on: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -52,6 +82,9 @@ pub struct Namespace {
     pub path: String,
     pub kind: String,
     pub full_path: String,
+    pub parent_id: Option<u64>,
+    pub avatar_url: Option<String>,
+    pub web_url: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -118,7 +151,7 @@ pub struct Project {
     pub star_count: u32,
     pub ci_config_path: Option<String>,
     #[serde(default)]
-    pub runners_token: Option<String>,
+    pub runners_token: Option<Secret>,
     pub public_jobs: bool,
     pub shared_with_groups: Vec<Group>,
     pub repository_storage: Option<String>,
@@ -230,7 +263,7 @@ impl GetProjectUsersOptionsBuilder {
     }
 }
 
-#[derive(Debug, Default, Serialize)]
+#[derive(Debug, Default, Clone, Serialize)]
 pub struct ProjectParams {
     pub name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -335,13 +368,118 @@ impl ProjectParamsBuilder {
     }
 
     pub fn build(&self) -> ProjectParams {
-        ProjectParams {
-            name: self.0.name.clone(),
-            ..Default::default()
-        }
+        self.0.clone()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Starrer {
+    pub starred_since: String,
+    pub user: User,
+}
+
+/// A project's share with a group, as returned when sharing.
+#[derive(Debug, Deserialize)]
+pub struct ProjectGroupLink {
+    pub id: u64,
+    pub project_id: u64,
+    pub group_id: u64,
+    pub group_access: AccessLevel,
+    pub expires_at: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct ForkParams {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub namespace_id: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub namespace_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub visibility: Option<String>,
+}
+
+impl ForkParams {
+    pub fn builder() -> ForkParamsBuilder {
+        ForkParamsBuilder::new()
+    }
+}
+
+pub struct ForkParamsBuilder(ForkParams);
+
+impl ForkParamsBuilder {
+    pub fn new() -> Self {
+        ForkParamsBuilder(ForkParams::default())
+    }
+
+    pub fn namespace_id(&mut self, id: u64) -> &mut Self {
+        self.0.namespace_id = Some(id);
+        self
+    }
+
+    pub fn namespace_path<T>(&mut self, path: T) -> &mut Self
+    where
+        T: Into<String>,
+    {
+        self.0.namespace_path = Some(path.into());
+        self
+    }
+
+    pub fn name<T>(&mut self, name: T) -> &mut Self
+    where
+        T: Into<String>,
+    {
+        self.0.name = Some(name.into());
+        self
+    }
+
+    pub fn path<T>(&mut self, path: T) -> &mut Self
+    where
+        T: Into<String>,
+    {
+        self.0.path = Some(path.into());
+        self
+    }
+
+    pub fn description<T>(&mut self, description: T) -> &mut Self
+    where
+        T: Into<String>,
+    {
+        self.0.description = Some(description.into());
+        self
+    }
+
+    pub fn visibility<T>(&mut self, visibility: T) -> &mut Self
+    where
+        T: Into<String>,
+    {
+        self.0.visibility = Some(visibility.into());
+        self
+    }
+
+    pub fn build(&self) -> ForkParams {
+        self.0.clone()
     }
 }
 
+#[derive(Serialize)]
+struct TransferParams {
+    namespace: String,
+}
+
+#[derive(Serialize)]
+struct ShareParams {
+    group_id: u64,
+    group_access: AccessLevel,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expires_at: Option<String>,
+}
+
 impl<'a> Projects<'a> {
     #[doc(hidden)]
     pub fn new(gitlab: &'a Gitlab) -> Projects<'a> {
@@ -377,11 +515,133 @@ impl<'a> Projects<'a> {
         self.gitlab.post::<Project>(&self.resource("", ""), json.into_bytes())
     }
 
-    pub fn delete<T>(&self, id: T)
+    pub fn delete<T>(&self, id: T) -> Result<()>
     where
         T: Into<String>
     {
         let encoded_id = utf8_percent_encode(&id.into(), PATH_SEGMENT_ENCODE_SET).to_string();
-        self.gitlab.delete(&self.resource(&encoded_id, ""));
+        self.gitlab.delete(&self.resource(&encoded_id, ""))
     }
-}
\ No newline at end of file
+
+    pub fn fork<T>(&self, id: T, params: &ForkParams) -> Result<Project>
+    where
+        T: Into<String>
+    {
+        let encoded_id = utf8_percent_encode(&id.into(), PATH_SEGMENT_ENCODE_SET).to_string();
+        let json = serde_json::to_string(&params)?;
+        self.gitlab.post::<Project>(&self.resource(&encoded_id, "/fork"), json.into_bytes())
+    }
+
+    pub fn forks<T>(&self, id: T) -> Result<Vec<Project>>
+    where
+        T: Into<String>
+    {
+        let encoded_id = utf8_percent_encode(&id.into(), PATH_SEGMENT_ENCODE_SET).to_string();
+        self.gitlab.get_all::<Project>(&self.resource(&encoded_id, "/forks"))
+    }
+
+    /// Mark an existing project as a fork of `forked_from_id`. Requires an
+    /// administrator or the project owner.
+    pub fn create_fork_relation<T>(&self, id: T, forked_from_id: u64) -> Result<()>
+    where
+        T: Into<String>
+    {
+        let encoded_id = utf8_percent_encode(&id.into(), PATH_SEGMENT_ENCODE_SET).to_string();
+        self.gitlab.post_without_response(
+            &self.resource(&encoded_id, &format!("/fork/{}", forked_from_id)),
+            Vec::new()
+        )
+    }
+
+    pub fn delete_fork_relation<T>(&self, id: T) -> Result<()>
+    where
+        T: Into<String>
+    {
+        let encoded_id = utf8_percent_encode(&id.into(), PATH_SEGMENT_ENCODE_SET).to_string();
+        self.gitlab.delete(&self.resource(&encoded_id, "/fork"))
+    }
+
+    /// Star a project. Returns `None` if it was already starred.
+    pub fn star<T>(&self, id: T) -> Result<Option<Project>>
+    where
+        T: Into<String>
+    {
+        let encoded_id = utf8_percent_encode(&id.into(), PATH_SEGMENT_ENCODE_SET).to_string();
+        self.gitlab.post_if_modified::<Project>(&self.resource(&encoded_id, "/star"), Vec::new())
+    }
+
+    /// Unstar a project. Returns `None` if it was not starred.
+    pub fn unstar<T>(&self, id: T) -> Result<Option<Project>>
+    where
+        T: Into<String>
+    {
+        let encoded_id = utf8_percent_encode(&id.into(), PATH_SEGMENT_ENCODE_SET).to_string();
+        self.gitlab.post_if_modified::<Project>(&self.resource(&encoded_id, "/unstar"), Vec::new())
+    }
+
+    pub fn starrers<T>(&self, id: T) -> Result<Vec<Starrer>>
+    where
+        T: Into<String>
+    {
+        let encoded_id = utf8_percent_encode(&id.into(), PATH_SEGMENT_ENCODE_SET).to_string();
+        self.gitlab.get_all::<Starrer>(&self.resource(&encoded_id, "/starrers"))
+    }
+
+    pub fn archive<T>(&self, id: T) -> Result<Project>
+    where
+        T: Into<String>
+    {
+        let encoded_id = utf8_percent_encode(&id.into(), PATH_SEGMENT_ENCODE_SET).to_string();
+        self.gitlab.post::<Project>(&self.resource(&encoded_id, "/archive"), Vec::new())
+    }
+
+    pub fn unarchive<T>(&self, id: T) -> Result<Project>
+    where
+        T: Into<String>
+    {
+        let encoded_id = utf8_percent_encode(&id.into(), PATH_SEGMENT_ENCODE_SET).to_string();
+        self.gitlab.post::<Project>(&self.resource(&encoded_id, "/unarchive"), Vec::new())
+    }
+
+    /// Move a project to another namespace, given by ID or path.
+    pub fn transfer<T, U>(&self, id: T, namespace: U) -> Result<Project>
+    where
+        T: Into<String>,
+        U: Into<String>,
+    {
+        let encoded_id = utf8_percent_encode(&id.into(), PATH_SEGMENT_ENCODE_SET).to_string();
+        let json = serde_json::to_string(&TransferParams { namespace: namespace.into() })?;
+        self.gitlab.put::<Project>(&self.resource(&encoded_id, "/transfer"), json.into_bytes())
+    }
+
+    /// Share a project with a group. `expires_at` is formatted `YYYY-MM-DD`.
+    pub fn share<T>(&self, id: T, group_id: u64, group_access: AccessLevel, expires_at: Option<String>) -> Result<ProjectGroupLink>
+    where
+        T: Into<String>
+    {
+        let encoded_id = utf8_percent_encode(&id.into(), PATH_SEGMENT_ENCODE_SET).to_string();
+        let json = serde_json::to_string(&ShareParams {
+            group_id: group_id,
+            group_access: group_access,
+            expires_at: expires_at,
+        })?;
+        self.gitlab.post::<ProjectGroupLink>(&self.resource(&encoded_id, "/share"), json.into_bytes())
+    }
+
+    pub fn unshare<T>(&self, id: T, group_id: u64) -> Result<()>
+    where
+        T: Into<String>
+    {
+        let encoded_id = utf8_percent_encode(&id.into(), PATH_SEGMENT_ENCODE_SET).to_string();
+        self.gitlab.delete(&self.resource(&encoded_id, &format!("/share/{}", group_id)))
+    }
+
+    /// Restore a project that is marked for deletion.
+    pub fn restore<T>(&self, id: T) -> Result<Project>
+    where
+        T: Into<String>
+    {
+        let encoded_id = utf8_percent_encode(&id.into(), PATH_SEGMENT_ENCODE_SET).to_string();
+        self.gitlab.post::<Project>(&self.resource(&encoded_id, "/restore"), Vec::new())
+    }
+}
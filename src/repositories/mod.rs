@@ -1,3 +1,21 @@
 extern crate serde_json;
 
-use self::super::{Gitlab, Error, ErrorKind, Result};
\ No newline at end of file
+use self::super::{Gitlab, Error, ErrorKind, Result};
+
+#[derive(Debug, Deserialize)]
+pub struct Commit {
+    pub id: String,
+    pub short_id: String,
+    pub title: String,
+    pub message: Option<String>,
+    pub author_name: String,
+    pub author_email: String,
+    pub authored_date: Option<String>,
+    pub committer_name: Option<String>,
+    pub committer_email: Option<String>,
+    pub committed_date: Option<String>,
+    pub created_at: String,
+    #[serde(default)]
+    pub parent_ids: Vec<String>,
+    pub web_url: Option<String>,
+}
@@ -0,0 +1,451 @@
+extern crate serde_json;
+
+use self::super::{Gitlab, Error, ErrorKind, Result};
+use pipelines::Pipeline;
+use projects::User;
+use secret::Secret;
+
+use url::percent_encoding::{utf8_percent_encode, PATH_SEGMENT_ENCODE_SET};
+use url::form_urlencoded;
+
+use std::collections::HashMap;
+
+/// A client for the [Runners API](https://docs.gitlab.com/ee/api/runners.html)
+pub struct Runners<'a> {
+    gitlab: &'a Gitlab,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RunnerProject {
+    pub id: u64,
+    pub name: String,
+    pub name_with_namespace: String,
+    pub path: String,
+    pub path_with_namespace: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RunnerGroup {
+    pub id: u64,
+    pub name: String,
+    pub web_url: String,
+}
+
+/// A runner. Listing endpoints only fill in the summary fields; the
+/// remaining fields are present when fetching a single runner.
+#[derive(Debug, Deserialize)]
+pub struct Runner {
+    pub id: u64,
+    pub description: Option<String>,
+    pub ip_address: Option<String>,
+    pub active: bool,
+    pub paused: Option<bool>,
+    pub is_shared: bool,
+    pub runner_type: String,
+    pub name: Option<String>,
+    pub online: Option<bool>,
+    pub status: String,
+    #[serde(default)]
+    pub tag_list: Vec<String>,
+    pub run_untagged: Option<bool>,
+    pub locked: Option<bool>,
+    pub maximum_timeout: Option<u64>,
+    pub access_level: Option<String>,
+    pub version: Option<String>,
+    pub revision: Option<String>,
+    pub platform: Option<String>,
+    pub architecture: Option<String>,
+    pub contacted_at: Option<String>,
+    #[serde(default)]
+    pub projects: Vec<RunnerProject>,
+    #[serde(default)]
+    pub groups: Vec<RunnerGroup>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Job {
+    pub id: u64,
+    pub status: String,
+    pub stage: String,
+    pub name: String,
+    #[serde(rename = "ref")]
+    pub ref_name: String,
+    pub tag: bool,
+    pub coverage: Option<f64>,
+    pub created_at: String,
+    pub started_at: Option<String>,
+    pub finished_at: Option<String>,
+    pub duration: Option<f64>,
+    pub user: Option<User>,
+    pub pipeline: Option<Pipeline>,
+    pub project: Option<RunnerProject>,
+    pub web_url: Option<String>,
+}
+
+/// A runner created with a registration token. `token` is the runner's
+/// authentication token and is only returned once.
+#[derive(Debug, Deserialize)]
+pub struct RegisteredRunner {
+    pub id: u64,
+    pub token: Secret,
+    pub token_expires_at: Option<String>,
+}
+
+#[derive(Default)]
+pub struct RunnersOptions {
+    pub params: HashMap<&'static str, String>,
+}
+
+impl RunnersOptions {
+    pub fn builder() -> RunnersOptionsBuilder {
+        RunnersOptionsBuilder::new()
+    }
+
+    pub fn to_query_string(&self) -> Option<String> {
+        if self.params.is_empty() {
+            None
+        } else {
+            let encoded_qs: String = form_urlencoded::Serializer::new(String::new())
+                .extend_pairs(&self.params)
+                .finish();
+            Some(encoded_qs)
+        }
+    }
+}
+
+pub struct RunnersOptionsBuilder(RunnersOptions);
+
+impl RunnersOptionsBuilder {
+    pub fn new() -> Self {
+        RunnersOptionsBuilder(RunnersOptions::default())
+    }
+
+    /// One of `instance_type`, `group_type` or `project_type`.
+    pub fn runner_type<T>(&mut self, runner_type: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.params.insert("type", runner_type.into());
+        self
+    }
+
+    /// One of `online`, `offline`, `stale` or `never_contacted`.
+    pub fn status<T>(&mut self, status: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.params.insert("status", status.into());
+        self
+    }
+
+    pub fn paused(&mut self, paused: bool) -> &mut Self {
+        self.0.params.insert("paused", paused.to_string());
+        self
+    }
+
+    pub fn tag_list(&mut self, tags: &[&str]) -> &mut Self {
+        self.0.params.insert("tag_list", tags.join(","));
+        self
+    }
+
+    pub fn build(&self) -> RunnersOptions {
+        RunnersOptions {
+            params: self.0.params.clone(),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct RunnerJobsOptions {
+    pub id: u64,
+    pub params: HashMap<&'static str, String>,
+}
+
+impl RunnerJobsOptions {
+    pub fn builder(id: u64) -> RunnerJobsOptionsBuilder {
+        RunnerJobsOptionsBuilder::new(id)
+    }
+
+    pub fn to_query_string(&self) -> Option<String> {
+        if self.params.is_empty() {
+            None
+        } else {
+            let encoded_qs: String = form_urlencoded::Serializer::new(String::new())
+                .extend_pairs(&self.params)
+                .finish();
+            Some(encoded_qs)
+        }
+    }
+}
+
+pub struct RunnerJobsOptionsBuilder(RunnerJobsOptions);
+
+impl RunnerJobsOptionsBuilder {
+    pub fn new(id: u64) -> Self {
+        RunnerJobsOptionsBuilder(RunnerJobsOptions {
+            id: id,
+            ..Default::default()
+        })
+    }
+
+    /// One of `running`, `success`, `failed` or `canceled`.
+    pub fn status<T>(&mut self, status: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.params.insert("status", status.into());
+        self
+    }
+
+    pub fn order_by<T>(&mut self, order_by: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.params.insert("order_by", order_by.into());
+        self
+    }
+
+    pub fn sort<T>(&mut self, sort: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.params.insert("sort", sort.into());
+        self
+    }
+
+    pub fn build(&self) -> RunnerJobsOptions {
+        RunnerJobsOptions {
+            id: self.0.id,
+            params: self.0.params.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct RunnerParams {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paused: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag_list: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub run_untagged: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locked: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub access_level: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maximum_timeout: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maintenance_note: Option<String>,
+}
+
+impl RunnerParams {
+    pub fn builder() -> RunnerParamsBuilder {
+        RunnerParamsBuilder::new()
+    }
+}
+
+pub struct RunnerParamsBuilder(RunnerParams);
+
+impl RunnerParamsBuilder {
+    pub fn new() -> Self {
+        RunnerParamsBuilder(RunnerParams::default())
+    }
+
+    pub fn description<T>(&mut self, description: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.description = Some(description.into());
+        self
+    }
+
+    pub fn paused(&mut self, paused: bool) -> &mut Self {
+        self.0.paused = Some(paused);
+        self
+    }
+
+    pub fn tag_list(&mut self, tags: Vec<String>) -> &mut Self {
+        self.0.tag_list = Some(tags);
+        self
+    }
+
+    pub fn run_untagged(&mut self, run_untagged: bool) -> &mut Self {
+        self.0.run_untagged = Some(run_untagged);
+        self
+    }
+
+    pub fn locked(&mut self, locked: bool) -> &mut Self {
+        self.0.locked = Some(locked);
+        self
+    }
+
+    /// Either `not_protected` or `ref_protected`.
+    pub fn access_level<T>(&mut self, access_level: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.access_level = Some(access_level.into());
+        self
+    }
+
+    pub fn maximum_timeout(&mut self, seconds: u64) -> &mut Self {
+        self.0.maximum_timeout = Some(seconds);
+        self
+    }
+
+    pub fn maintenance_note<T>(&mut self, note: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.maintenance_note = Some(note.into());
+        self
+    }
+
+    pub fn build(&self) -> RunnerParams {
+        self.0.clone()
+    }
+}
+
+/// Parameters for registering a runner with a registration token.
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct RegisterRunnerParams {
+    pub token: Secret,
+    #[serde(flatten)]
+    pub runner: RunnerParams,
+}
+
+#[derive(Serialize)]
+struct RunnerToken<'a> {
+    token: &'a Secret,
+}
+
+#[derive(Serialize)]
+struct RunnerId {
+    runner_id: u64,
+}
+
+/// Runner tokens are sent as a form rather than in the query string, so
+/// that they stay out of access logs.
+fn token_form(token: Secret) -> Vec<(String, String)> {
+    vec![(String::from("token"), token.into_inner())]
+}
+
+impl<'a> Runners<'a> {
+    #[doc(hidden)]
+    pub fn new(gitlab: &'a Gitlab) -> Runners<'a> {
+        Runners {
+            gitlab: gitlab,
+        }
+    }
+
+    fn resource(&self, more: &str) -> String {
+        format!("/runners{}", more)
+    }
+
+    fn project_resource(&self, id: &str, more: &str) -> String {
+        let encoded_id = utf8_percent_encode(id, PATH_SEGMENT_ENCODE_SET).to_string();
+        format!("/projects/{}/runners{}", encoded_id, more)
+    }
+
+    fn list_resource(&self, base: String, options: &RunnersOptions) -> Result<Vec<Runner>> {
+        let mut uri = vec![base];
+        if let Some(query) = options.to_query_string() {
+            uri.push(query);
+        }
+        self.gitlab.get_all::<Runner>(&uri.join("?"))
+    }
+
+    /// Runners available to the authenticated user.
+    pub fn owned(&self, options: &RunnersOptions) -> Result<Vec<Runner>> {
+        self.list_resource(self.resource(""), options)
+    }
+
+    /// Every runner on the instance. Requires an administrator.
+    pub fn all(&self, options: &RunnersOptions) -> Result<Vec<Runner>> {
+        self.list_resource(self.resource("/all"), options)
+    }
+
+    pub fn runner(&self, id: u64) -> Result<Runner> {
+        self.gitlab.get::<Runner>(&self.resource(&format!("/{}", id)))
+    }
+
+    pub fn update(&self, id: u64, params: &RunnerParams) -> Result<Runner> {
+        let json = serde_json::to_string(&params)?;
+        self.gitlab.put::<Runner>(&self.resource(&format!("/{}", id)), json.into_bytes())
+    }
+
+    pub fn remove(&self, id: u64) -> Result<()> {
+        self.gitlab.delete(&self.resource(&format!("/{}", id)))
+    }
+
+    pub fn jobs(&self, options: &RunnerJobsOptions) -> Result<Vec<Job>> {
+        let mut uri = vec![self.resource(&format!("/{}/jobs", options.id))];
+        if let Some(query) = options.to_query_string() {
+            uri.push(query);
+        }
+        self.gitlab.get_all::<Job>(&uri.join("?"))
+    }
+
+    pub fn project_runners<T>(&self, id: T, options: &RunnersOptions) -> Result<Vec<Runner>>
+        where T: Into<String>
+    {
+        self.list_resource(self.project_resource(&id.into(), ""), options)
+    }
+
+    pub fn enable_for_project<T>(&self, id: T, runner_id: u64) -> Result<Runner>
+        where T: Into<String>
+    {
+        let json = serde_json::to_string(&RunnerId { runner_id: runner_id })?;
+        self.gitlab.post::<Runner>(&self.project_resource(&id.into(), ""), json.into_bytes())
+    }
+
+    pub fn disable_for_project<T>(&self, id: T, runner_id: u64) -> Result<()>
+        where T: Into<String>
+    {
+        self.gitlab.delete(&self.project_resource(&id.into(), &format!("/{}", runner_id)))
+    }
+
+    /// Register a new runner using a registration token.
+    pub fn register(&self, params: &RegisterRunnerParams) -> Result<RegisteredRunner> {
+        let json = serde_json::to_string(&params)?;
+        self.gitlab.post::<RegisteredRunner>(&self.resource(""), json.into_bytes())
+    }
+
+    /// Check that a runner authentication token is still valid.
+    pub fn verify<T>(&self, token: T) -> Result<()>
+        where T: Into<Secret>
+    {
+        let token = token.into();
+        let json = serde_json::to_string(&RunnerToken { token: &token })?;
+        self.gitlab.post_without_response(&self.resource("/verify"), json.into_bytes())
+    }
+
+    /// Delete the runner identified by an authentication token.
+    pub fn delete_by_token<T>(&self, token: T) -> Result<()>
+        where T: Into<Secret>
+    {
+        self.gitlab.delete_form(&self.resource(""), &token_form(token.into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{token_form, RegisterRunnerParams, RunnerParams};
+    use form_body;
+    use serde_json;
+
+    #[test]
+    fn register_params_are_flat() {
+        let params = RegisterRunnerParams {
+            token: "glrt-registration".into(),
+            runner: RunnerParams::builder()
+                .description("builder")
+                .tag_list(vec![String::from("docker")])
+                .run_untagged(false)
+                .build(),
+        };
+        assert_eq!(serde_json::to_string(&params).unwrap(),
+                   r#"{"token":"glrt-registration","description":"builder","tag_list":["docker"],"run_untagged":false}"#);
+        assert!(!format!("{:?}", params).contains("glrt-registration"));
+    }
+
+    #[test]
+    fn delete_token_is_form_encoded() {
+        assert_eq!(form_body(&token_form("glrt-a+b/c=".into())), "token=glrt-a%2Bb%2Fc%3D");
+    }
+}
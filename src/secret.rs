@@ -0,0 +1,72 @@
+//! Wrapper for sensitive values such as tokens and CI/CD variable values.
+
+use std::fmt;
+
+use serde::{Serialize, Serializer, Deserialize, Deserializer};
+
+/// A string that is redacted when formatted with `Debug`.
+///
+/// Serializes and deserializes as a plain string so it can be used directly
+/// in API models. Call `expose` to get at the underlying value.
+#[derive(Clone, PartialEq, Eq, Default)]
+pub struct Secret(String);
+
+impl Secret {
+    pub fn new<T: Into<String>>(value: T) -> Secret {
+        Secret(value.into())
+    }
+
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Secret([REDACTED])")
+    }
+}
+
+impl From<String> for Secret {
+    fn from(value: String) -> Secret {
+        Secret(value)
+    }
+}
+
+impl<'a> From<&'a str> for Secret {
+    fn from(value: &'a str) -> Secret {
+        Secret(value.to_owned())
+    }
+}
+
+impl Serialize for Secret {
+    fn serialize<S>(&self, serializer: S) -> ::std::result::Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Secret {
+    fn deserialize<D>(deserializer: D) -> ::std::result::Result<Secret, D::Error>
+        where D: Deserializer<'de>
+    {
+        String::deserialize(deserializer).map(Secret)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Secret;
+
+    #[test]
+    fn debug_is_redacted() {
+        let secret = Secret::new("glpat-hunter2");
+        assert!(!format!("{:?}", secret).contains("hunter2"));
+        assert_eq!(secret.expose(), "glpat-hunter2");
+    }
+}
@@ -0,0 +1,438 @@
+extern crate serde_json;
+
+use self::super::{Gitlab, Error, ErrorKind, Result};
+
+use url::percent_encoding::{utf8_percent_encode, PATH_SEGMENT_ENCODE_SET};
+use url::form_urlencoded;
+
+use std::collections::HashMap;
+use std::env;
+use std::fs::{self, File};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+/// A client for one kind of instance-wide
+/// [template](https://docs.gitlab.com/ee/api/templates/gitignores.html).
+pub struct Templates<'a> {
+    gitlab: &'a Gitlab,
+    kind: TemplateKind,
+    cache: Option<TemplateCache>,
+}
+
+/// A client for [license templates](https://docs.gitlab.com/ee/api/templates/licenses.html)
+pub struct Licenses<'a> {
+    gitlab: &'a Gitlab,
+}
+
+/// A client for [project-level templates](https://docs.gitlab.com/ee/api/project_templates.html)
+pub struct ProjectTemplates<'a> {
+    gitlab: &'a Gitlab,
+}
+
+/// Kinds of instance-wide template.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TemplateKind {
+    Dockerfiles,
+    Gitignores,
+    GitlabCiYmls,
+    Licenses,
+}
+
+impl TemplateKind {
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            TemplateKind::Dockerfiles => "dockerfiles",
+            TemplateKind::Gitignores => "gitignores",
+            TemplateKind::GitlabCiYmls => "gitlab_ci_ymls",
+            TemplateKind::Licenses => "licenses",
+        }
+    }
+}
+
+/// Kinds of project-level template: the instance-wide kinds plus issue and
+/// merge request description templates.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProjectTemplateKind {
+    Dockerfiles,
+    Gitignores,
+    GitlabCiYmls,
+    Licenses,
+    Issues,
+    MergeRequests,
+}
+
+impl ProjectTemplateKind {
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            ProjectTemplateKind::Dockerfiles => "dockerfiles",
+            ProjectTemplateKind::Gitignores => "gitignores",
+            ProjectTemplateKind::GitlabCiYmls => "gitlab_ci_ymls",
+            ProjectTemplateKind::Licenses => "licenses",
+            ProjectTemplateKind::Issues => "issues",
+            ProjectTemplateKind::MergeRequests => "merge_requests",
+        }
+    }
+}
+
+impl From<TemplateKind> for ProjectTemplateKind {
+    fn from(kind: TemplateKind) -> ProjectTemplateKind {
+        match kind {
+            TemplateKind::Dockerfiles => ProjectTemplateKind::Dockerfiles,
+            TemplateKind::Gitignores => ProjectTemplateKind::Gitignores,
+            TemplateKind::GitlabCiYmls => ProjectTemplateKind::GitlabCiYmls,
+            TemplateKind::Licenses => ProjectTemplateKind::Licenses,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Template {
+    pub name: String,
+    pub content: Option<String>,
+}
+
+/// Entry in a list of available templates. `key` is what fetching a single
+/// template expects.
+#[derive(Debug, Deserialize)]
+pub struct TemplateKey {
+    pub key: String,
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct License {
+    pub key: String,
+    pub name: String,
+    pub nickname: Option<String>,
+    #[serde(default)]
+    pub popular: bool,
+    pub html_url: Option<String>,
+    pub source_url: Option<String>,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub conditions: Vec<String>,
+    #[serde(default)]
+    pub permissions: Vec<String>,
+    #[serde(default)]
+    pub limitations: Vec<String>,
+    pub content: Option<String>,
+}
+
+/// On-disk cache of fetched templates, one JSON file per template.
+///
+/// Entries younger than `ttl` are used without contacting GitLab. Older
+/// entries are refreshed, but are still returned if GitLab cannot be
+/// reached, so templates remain available offline once fetched.
+#[derive(Debug, Clone)]
+pub struct TemplateCache {
+    dir: PathBuf,
+    ttl: Duration,
+}
+
+impl TemplateCache {
+    pub fn new<P>(dir: P, ttl: Duration) -> TemplateCache
+        where P: Into<PathBuf> {
+        TemplateCache {
+            dir: dir.into(),
+            ttl: ttl,
+        }
+    }
+
+    /// A cache under `$XDG_CACHE_HOME/gitlab-rs/<kind>`, falling back to
+    /// `$HOME/.cache/gitlab-rs/<kind>`.
+    pub fn default_location(kind: &str, ttl: Duration) -> Option<TemplateCache> {
+        let base = env::var_os("XDG_CACHE_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")));
+        base.map(|base| TemplateCache::new(base.join("gitlab-rs").join(kind), ttl))
+    }
+
+    fn path(&self, name: &str) -> PathBuf {
+        let file_name: String = name.chars()
+            .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' || c == '.' { c } else { '_' })
+            .collect();
+        self.dir.join(format!("{}.json", file_name))
+    }
+
+    fn is_fresh(&self, name: &str) -> bool {
+        fs::metadata(self.path(name))
+            .and_then(|meta| meta.modified())
+            .ok()
+            .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+            .map(|age| age < self.ttl)
+            .unwrap_or(false)
+    }
+
+    /// Read a cached template regardless of its age.
+    pub fn read(&self, name: &str) -> Option<Template> {
+        File::open(self.path(name))
+            .ok()
+            .and_then(|file| serde_json::from_reader(file).ok())
+    }
+
+    pub fn write(&self, name: &str, template: &Template) -> Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        let file = File::create(self.path(name))?;
+        serde_json::to_writer(file, template)?;
+        Ok(())
+    }
+}
+
+/// Options for rendering a license template.
+#[derive(Default)]
+pub struct LicenseOptions {
+    pub key: String,
+    pub params: HashMap<&'static str, String>,
+}
+
+impl LicenseOptions {
+    pub fn builder<T: Into<String>>(key: T) -> LicenseOptionsBuilder {
+        LicenseOptionsBuilder::new(key)
+    }
+
+    pub fn to_query_string(&self) -> Option<String> {
+        if self.params.is_empty() {
+            None
+        } else {
+            let encoded_qs: String = form_urlencoded::Serializer::new(String::new())
+                .extend_pairs(&self.params)
+                .finish();
+            Some(encoded_qs)
+        }
+    }
+}
+
+pub struct LicenseOptionsBuilder(LicenseOptions);
+
+impl LicenseOptionsBuilder {
+    pub fn new<T>(key: T) -> Self
+        where T: Into<String>,
+    {
+        LicenseOptionsBuilder(LicenseOptions {
+            key: key.into(),
+            ..Default::default()
+        })
+    }
+
+    /// Project name substituted into the license text.
+    pub fn project<T>(&mut self, project: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.params.insert("project", project.into());
+        self
+    }
+
+    /// Copyright holder substituted into the license text.
+    pub fn fullname<T>(&mut self, fullname: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.params.insert("fullname", fullname.into());
+        self
+    }
+
+    pub fn build(&self) -> LicenseOptions {
+        LicenseOptions {
+            key: self.0.key.clone(),
+            params: self.0.params.clone(),
+        }
+    }
+}
+
+/// Options for fetching a single project-level template.
+pub struct ProjectTemplateOptions {
+    pub id: String,
+    pub kind: ProjectTemplateKind,
+    pub name: String,
+    pub params: HashMap<&'static str, String>,
+}
+
+impl ProjectTemplateOptions {
+    pub fn builder<T, U>(id: T, kind: ProjectTemplateKind, name: U) -> ProjectTemplateOptionsBuilder
+        where T: Into<String>,
+              U: Into<String>,
+    {
+        ProjectTemplateOptionsBuilder::new(id, kind, name)
+    }
+
+    pub fn to_query_string(&self) -> Option<String> {
+        if self.params.is_empty() {
+            None
+        } else {
+            let encoded_qs: String = form_urlencoded::Serializer::new(String::new())
+                .extend_pairs(&self.params)
+                .finish();
+            Some(encoded_qs)
+        }
+    }
+}
+
+pub struct ProjectTemplateOptionsBuilder(ProjectTemplateOptions);
+
+impl ProjectTemplateOptionsBuilder {
+    pub fn new<T, U>(id: T, kind: ProjectTemplateKind, name: U) -> Self
+        where T: Into<String>,
+              U: Into<String>,
+    {
+        ProjectTemplateOptionsBuilder(ProjectTemplateOptions {
+            id: id.into(),
+            kind: kind,
+            name: name.into(),
+            params: HashMap::new(),
+        })
+    }
+
+    /// Project to take custom templates from.
+    pub fn source_template_project_id(&mut self, id: u64) -> &mut Self {
+        self.0.params.insert("source_template_project_id", id.to_string());
+        self
+    }
+
+    /// Project name substituted into a license template.
+    pub fn project<T>(&mut self, project: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.params.insert("project", project.into());
+        self
+    }
+
+    /// Copyright holder substituted into a license template.
+    pub fn fullname<T>(&mut self, fullname: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.params.insert("fullname", fullname.into());
+        self
+    }
+
+    pub fn build(&self) -> ProjectTemplateOptions {
+        ProjectTemplateOptions {
+            id: self.0.id.clone(),
+            kind: self.0.kind,
+            name: self.0.name.clone(),
+            params: self.0.params.clone(),
+        }
+    }
+}
+
+impl<'a> Templates<'a> {
+    #[doc(hidden)]
+    pub fn new(gitlab: &'a Gitlab, kind: TemplateKind) -> Templates<'a> {
+        Templates {
+            gitlab: gitlab,
+            kind: kind,
+            cache: None,
+        }
+    }
+
+    /// Serve templates from `cache` where possible.
+    pub fn with_cache(mut self, cache: TemplateCache) -> Templates<'a> {
+        self.cache = Some(cache);
+        self
+    }
+
+    fn resource(&self, more: &str) -> String {
+        format!("/templates/{}{}", self.kind.as_str(), more)
+    }
+
+    /// List the keys and names of every available template.
+    pub fn list(&self) -> Result<Vec<TemplateKey>> {
+        self.gitlab.get_all::<TemplateKey>(&self.resource(""))
+    }
+
+    pub fn template<T>(&self, name: T) -> Result<Template>
+        where T: Into<String> {
+        let name = name.into();
+
+        let cache = match self.cache {
+            Some(ref cache) => cache,
+            None => return self.fetch(&name),
+        };
+
+        if cache.is_fresh(&name) {
+            if let Some(template) = cache.read(&name) {
+                return Ok(template);
+            }
+        }
+
+        match self.fetch(&name) {
+            Ok(template) => {
+                if let Err(e) = cache.write(&name, &template) {
+                    warn!("Unable to cache {} template {}: {}", self.kind.as_str(), name, e);
+                }
+                Ok(template)
+            }
+            Err(e) => cache.read(&name).ok_or(e),
+        }
+    }
+
+    fn fetch(&self, name: &str) -> Result<Template> {
+        let encoded_name = utf8_percent_encode(name, PATH_SEGMENT_ENCODE_SET).to_string();
+        self.gitlab.get::<Template>(&self.resource(&format!("/{}", encoded_name)))
+    }
+}
+
+impl<'a> Licenses<'a> {
+    #[doc(hidden)]
+    pub fn new(gitlab: &'a Gitlab) -> Licenses<'a> {
+        Licenses {
+            gitlab: gitlab,
+        }
+    }
+
+    /// List license templates, only the popular ones if `popular` is set.
+    pub fn list(&self, popular: bool) -> Result<Vec<License>> {
+        if popular {
+            self.gitlab.get_all::<License>("/templates/licenses?popular=true")
+        } else {
+            self.gitlab.get_all::<License>("/templates/licenses")
+        }
+    }
+
+    pub fn license(&self, options: &LicenseOptions) -> Result<License> {
+        let encoded_key = utf8_percent_encode(&options.key, PATH_SEGMENT_ENCODE_SET).to_string();
+        let mut uri = vec![format!("/templates/licenses/{}", encoded_key)];
+        if let Some(query) = options.to_query_string() {
+            uri.push(query);
+        }
+        self.gitlab.get::<License>(&uri.join("?"))
+    }
+}
+
+impl<'a> ProjectTemplates<'a> {
+    #[doc(hidden)]
+    pub fn new(gitlab: &'a Gitlab) -> ProjectTemplates<'a> {
+        ProjectTemplates {
+            gitlab: gitlab,
+        }
+    }
+
+    fn resource(&self, id: &str, kind: ProjectTemplateKind, more: &str) -> String {
+        let encoded_id = utf8_percent_encode(id, PATH_SEGMENT_ENCODE_SET).to_string();
+        format!("/projects/{}/templates/{}{}", encoded_id, kind.as_str(), more)
+    }
+
+    fn single_resource(&self, options: &ProjectTemplateOptions) -> String {
+        let encoded_name = utf8_percent_encode(&options.name, PATH_SEGMENT_ENCODE_SET).to_string();
+        let mut uri = vec![self.resource(&options.id, options.kind, &format!("/{}", encoded_name))];
+        if let Some(query) = options.to_query_string() {
+            uri.push(query);
+        }
+        uri.join("?")
+    }
+
+    /// List the templates of one kind available to a project, including
+    /// custom templates from its instance or group.
+    pub fn list<T>(&self, id: T, kind: ProjectTemplateKind) -> Result<Vec<TemplateKey>>
+        where T: Into<String>
+    {
+        self.gitlab.get_all::<TemplateKey>(&self.resource(&id.into(), kind, ""))
+    }
+
+    pub fn template(&self, options: &ProjectTemplateOptions) -> Result<Template> {
+        self.gitlab.get::<Template>(&self.single_resource(options))
+    }
+
+    /// Fetch a license template with the project's substitutions applied.
+    pub fn license(&self, options: &ProjectTemplateOptions) -> Result<License> {
+        self.gitlab.get::<License>(&self.single_resource(options))
+    }
+}
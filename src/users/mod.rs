@@ -0,0 +1,459 @@
+extern crate serde_json;
+
+use self::super::{Gitlab, Error, ErrorKind, Result};
+use projects::User;
+use secret::Secret;
+
+use url::form_urlencoded;
+
+use std::collections::HashMap;
+
+/// A client for the [Users API](https://docs.gitlab.com/ee/api/users.html)
+pub struct Users<'a> {
+    gitlab: &'a Gitlab,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SshKey {
+    pub id: u64,
+    pub title: String,
+    pub key: String,
+    pub created_at: Option<String>,
+    pub expires_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GpgKey {
+    pub id: u64,
+    pub key: String,
+    pub created_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Email {
+    pub id: u64,
+    pub email: String,
+    pub confirmed_at: Option<String>,
+}
+
+/// An impersonation token. `token` is only present in the response to
+/// creating the token.
+#[derive(Debug, Deserialize)]
+pub struct ImpersonationToken {
+    pub id: u64,
+    pub name: String,
+    pub user_id: u64,
+    pub active: bool,
+    pub revoked: bool,
+    pub impersonation: bool,
+    #[serde(default)]
+    pub scopes: Vec<String>,
+    pub created_at: String,
+    pub expires_at: Option<String>,
+    pub token: Option<Secret>,
+}
+
+#[derive(Default)]
+pub struct UsersOptions {
+    pub params: HashMap<&'static str, String>,
+}
+
+impl UsersOptions {
+    pub fn builder() -> UsersOptionsBuilder {
+        UsersOptionsBuilder::new()
+    }
+
+    pub fn to_query_string(&self) -> Option<String> {
+        if self.params.is_empty() {
+            None
+        } else {
+            let encoded_qs: String = form_urlencoded::Serializer::new(String::new())
+                .extend_pairs(&self.params)
+                .finish();
+            Some(encoded_qs)
+        }
+    }
+}
+
+pub struct UsersOptionsBuilder(UsersOptions);
+
+impl UsersOptionsBuilder {
+    pub fn new() -> Self {
+        UsersOptionsBuilder(UsersOptions::default())
+    }
+
+    /// Search by name, username or (for administrators) email.
+    pub fn search<T>(&mut self, search: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.params.insert("search", search.into());
+        self
+    }
+
+    pub fn username<T>(&mut self, username: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.params.insert("username", username.into());
+        self
+    }
+
+    pub fn active(&mut self, active: bool) -> &mut Self {
+        self.0.params.insert("active", active.to_string());
+        self
+    }
+
+    pub fn blocked(&mut self, blocked: bool) -> &mut Self {
+        self.0.params.insert("blocked", blocked.to_string());
+        self
+    }
+
+    pub fn external(&mut self, external: bool) -> &mut Self {
+        self.0.params.insert("external", external.to_string());
+        self
+    }
+
+    pub fn build(&self) -> UsersOptions {
+        UsersOptions {
+            params: self.0.params.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct UserParams {
+    pub email: String,
+    pub username: String,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<Secret>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reset_password: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skip_confirmation: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub admin: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_create_group: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub projects_limit: Option<u32>,
+}
+
+impl UserParams {
+    pub fn builder<T, U, V>(email: T, username: U, name: V) -> UserParamsBuilder
+        where T: Into<String>,
+              U: Into<String>,
+              V: Into<String>,
+    {
+        UserParamsBuilder::new(email, username, name)
+    }
+}
+
+pub struct UserParamsBuilder(UserParams);
+
+impl UserParamsBuilder {
+    pub fn new<T, U, V>(email: T, username: U, name: V) -> Self
+        where T: Into<String>,
+              U: Into<String>,
+              V: Into<String>,
+    {
+        UserParamsBuilder(UserParams {
+            email: email.into(),
+            username: username.into(),
+            name: name.into(),
+            ..Default::default()
+        })
+    }
+
+    pub fn password<T>(&mut self, password: T) -> &mut Self
+        where T: Into<Secret>
+    {
+        self.0.password = Some(password.into());
+        self
+    }
+
+    /// Send the user a password reset link instead of setting a password.
+    pub fn reset_password(&mut self, reset: bool) -> &mut Self {
+        self.0.reset_password = Some(reset);
+        self
+    }
+
+    pub fn skip_confirmation(&mut self, skip: bool) -> &mut Self {
+        self.0.skip_confirmation = Some(skip);
+        self
+    }
+
+    pub fn admin(&mut self, admin: bool) -> &mut Self {
+        self.0.admin = Some(admin);
+        self
+    }
+
+    pub fn external(&mut self, external: bool) -> &mut Self {
+        self.0.external = Some(external);
+        self
+    }
+
+    pub fn can_create_group(&mut self, can_create_group: bool) -> &mut Self {
+        self.0.can_create_group = Some(can_create_group);
+        self
+    }
+
+    pub fn projects_limit(&mut self, limit: u32) -> &mut Self {
+        self.0.projects_limit = Some(limit);
+        self
+    }
+
+    pub fn build(&self) -> UserParams {
+        self.0.clone()
+    }
+}
+
+/// Parameters for modifying a user; only the fields that are set change.
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct EditUserParams {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<Secret>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skip_reconfirmation: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub admin: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_create_group: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub projects_limit: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SshKeyParams {
+    pub title: String,
+    pub key: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<String>,
+}
+
+#[derive(Serialize)]
+struct GpgKeyParams<'a> {
+    key: &'a str,
+}
+
+#[derive(Serialize)]
+struct EmailParams<'a> {
+    email: &'a str,
+    skip_confirmation: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ImpersonationTokenParams {
+    pub name: String,
+    pub scopes: Vec<String>,
+    /// Date the token expires, formatted `YYYY-MM-DD`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<String>,
+}
+
+fn with_query(resource: String, params: &[(&str, &str)]) -> String {
+    if params.is_empty() {
+        return resource;
+    }
+    let query: String = form_urlencoded::Serializer::new(String::new())
+        .extend_pairs(params)
+        .finish();
+    format!("{}?{}", resource, query)
+}
+
+/// The `username` filter also matches other users in some Gitlab versions,
+/// so pick out the exact match.
+fn find_username(users: Vec<User>, username: &str) -> Option<User> {
+    users.into_iter().find(|user| {
+        user.username.as_ref().map_or(false, |name| name.eq_ignore_ascii_case(username))
+    })
+}
+
+impl<'a> Users<'a> {
+    #[doc(hidden)]
+    pub fn new(gitlab: &'a Gitlab) -> Users<'a> {
+        Users {
+            gitlab: gitlab,
+        }
+    }
+
+    fn resource(&self, id: u64, more: &str) -> String {
+        format!("/users/{}{}", id, more)
+    }
+
+    /// The authenticated user.
+    pub fn current(&self) -> Result<User> {
+        self.gitlab.get::<User>("/user")
+    }
+
+    pub fn list(&self, options: &UsersOptions) -> Result<Vec<User>> {
+        let mut uri = vec![String::from("/users")];
+        if let Some(query) = options.to_query_string() {
+            uri.push(query);
+        }
+        self.gitlab.get_all::<User>(&uri.join("?"))
+    }
+
+    pub fn user(&self, id: u64) -> Result<User> {
+        self.gitlab.get::<User>(&self.resource(id, ""))
+    }
+
+    /// Look up a user by username, ignoring case as Gitlab does. Returns
+    /// `None` if there is no such user.
+    pub fn user_by_username<T>(&self, username: T) -> Result<Option<User>>
+        where T: Into<String>
+    {
+        let username = username.into();
+        let options = UsersOptions::builder().username(username.clone()).build();
+        Ok(find_username(self.list(&options)?, &username))
+    }
+
+    pub fn create(&self, params: &UserParams) -> Result<User> {
+        let json = serde_json::to_string(&params)?;
+        self.gitlab.post::<User>("/users", json.into_bytes())
+    }
+
+    pub fn modify(&self, id: u64, params: &EditUserParams) -> Result<User> {
+        let json = serde_json::to_string(&params)?;
+        self.gitlab.put::<User>(&self.resource(id, ""), json.into_bytes())
+    }
+
+    /// Delete a user. With `hard_delete`, contributions are removed rather
+    /// than moved to the ghost user.
+    pub fn delete(&self, id: u64, hard_delete: bool) -> Result<()> {
+        let params: &[(&str, &str)] = if hard_delete {
+            &[("hard_delete", "true")]
+        } else {
+            &[]
+        };
+        self.gitlab.delete(&with_query(self.resource(id, ""), params))
+    }
+
+    pub fn block(&self, id: u64) -> Result<bool> {
+        self.gitlab.post::<bool>(&self.resource(id, "/block"), Vec::new())
+    }
+
+    pub fn unblock(&self, id: u64) -> Result<bool> {
+        self.gitlab.post::<bool>(&self.resource(id, "/unblock"), Vec::new())
+    }
+
+    pub fn deactivate(&self, id: u64) -> Result<bool> {
+        self.gitlab.post::<bool>(&self.resource(id, "/deactivate"), Vec::new())
+    }
+
+    pub fn activate(&self, id: u64) -> Result<bool> {
+        self.gitlab.post::<bool>(&self.resource(id, "/activate"), Vec::new())
+    }
+
+    pub fn ssh_keys(&self, id: u64) -> Result<Vec<SshKey>> {
+        self.gitlab.get_all::<SshKey>(&self.resource(id, "/keys"))
+    }
+
+    pub fn add_ssh_key(&self, id: u64, params: &SshKeyParams) -> Result<SshKey> {
+        let json = serde_json::to_string(&params)?;
+        self.gitlab.post::<SshKey>(&self.resource(id, "/keys"), json.into_bytes())
+    }
+
+    pub fn delete_ssh_key(&self, id: u64, key_id: u64) -> Result<()> {
+        self.gitlab.delete(&self.resource(id, &format!("/keys/{}", key_id)))
+    }
+
+    pub fn gpg_keys(&self, id: u64) -> Result<Vec<GpgKey>> {
+        self.gitlab.get_all::<GpgKey>(&self.resource(id, "/gpg_keys"))
+    }
+
+    pub fn add_gpg_key<T>(&self, id: u64, key: T) -> Result<GpgKey>
+        where T: Into<String>
+    {
+        let key = key.into();
+        let json = serde_json::to_string(&GpgKeyParams { key: &key })?;
+        self.gitlab.post::<GpgKey>(&self.resource(id, "/gpg_keys"), json.into_bytes())
+    }
+
+    pub fn delete_gpg_key(&self, id: u64, key_id: u64) -> Result<()> {
+        self.gitlab.delete(&self.resource(id, &format!("/gpg_keys/{}", key_id)))
+    }
+
+    pub fn emails(&self, id: u64) -> Result<Vec<Email>> {
+        self.gitlab.get_all::<Email>(&self.resource(id, "/emails"))
+    }
+
+    pub fn add_email<T>(&self, id: u64, email: T, skip_confirmation: bool) -> Result<Email>
+        where T: Into<String>
+    {
+        let email = email.into();
+        let json = serde_json::to_string(&EmailParams {
+            email: &email,
+            skip_confirmation: skip_confirmation,
+        })?;
+        self.gitlab.post::<Email>(&self.resource(id, "/emails"), json.into_bytes())
+    }
+
+    pub fn delete_email(&self, id: u64, email_id: u64) -> Result<()> {
+        self.gitlab.delete(&self.resource(id, &format!("/emails/{}", email_id)))
+    }
+
+    /// List impersonation tokens, optionally filtered by `active` or `inactive`.
+    pub fn impersonation_tokens(&self, id: u64, state: Option<&str>) -> Result<Vec<ImpersonationToken>> {
+        let params: Vec<(&str, &str)> = state.into_iter().map(|state| ("state", state)).collect();
+        self.gitlab.get_all::<ImpersonationToken>(
+            &with_query(self.resource(id, "/impersonation_tokens"), &params)
+        )
+    }
+
+    pub fn impersonation_token(&self, id: u64, token_id: u64) -> Result<ImpersonationToken> {
+        self.gitlab.get::<ImpersonationToken>(
+            &self.resource(id, &format!("/impersonation_tokens/{}", token_id))
+        )
+    }
+
+    pub fn create_impersonation_token(&self, id: u64, params: &ImpersonationTokenParams) -> Result<ImpersonationToken> {
+        let json = serde_json::to_string(&params)?;
+        self.gitlab.post::<ImpersonationToken>(
+            &self.resource(id, "/impersonation_tokens"),
+            json.into_bytes()
+        )
+    }
+
+    pub fn revoke_impersonation_token(&self, id: u64, token_id: u64) -> Result<()> {
+        self.gitlab.delete(&self.resource(id, &format!("/impersonation_tokens/{}", token_id)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_username, with_query};
+    use projects::User;
+    use serde_json;
+
+    fn user(id: u64, username: &str) -> User {
+        let json = format!(r#"{{"id": {}, "name": "{1}", "username": "{1}"}}"#, id, username);
+        serde_json::from_str(&json).unwrap()
+    }
+
+    #[test]
+    fn query_is_encoded() {
+        assert_eq!(with_query(String::from("/users/3"), &[]), "/users/3");
+        assert_eq!(with_query(String::from("/users/3"), &[("hard_delete", "true")]),
+                   "/users/3?hard_delete=true");
+        assert_eq!(with_query(String::from("/users/3/impersonation_tokens"), &[("state", "in active&x")]),
+                   "/users/3/impersonation_tokens?state=in+active%26x");
+    }
+
+    #[test]
+    fn username_must_match_exactly() {
+        let users = vec![user(1, "alice2"), user(2, "Alice")];
+        assert_eq!(find_username(users, "alice").map(|u| u.id), Some(2));
+        assert!(find_username(vec![user(1, "alice2")], "alice").is_none());
+        assert!(find_username(Vec::new(), "alice").is_none());
+    }
+}
@@ -0,0 +1,255 @@
+extern crate serde_json;
+
+use self::super::{Gitlab, Error, ErrorKind, Result};
+use secret::Secret;
+
+use url::percent_encoding::{utf8_percent_encode, PATH_SEGMENT_ENCODE_SET};
+use url::form_urlencoded;
+
+/// A client for [CI/CD variables](https://docs.gitlab.com/ee/api/project_level_variables.html)
+/// at project, group or instance level.
+pub struct Variables<'a> {
+    gitlab: &'a Gitlab,
+    scope: VariableScope,
+}
+
+/// Where a set of variables is defined.
+#[derive(Debug, Clone, PartialEq)]
+pub enum VariableScope {
+    /// Project ID or namespaced path
+    Project(String),
+
+    /// Group ID or full path
+    Group(String),
+
+    /// Instance-wide variables, which require an administrator
+    Instance,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VariableType {
+    EnvVar,
+    File,
+}
+
+impl Default for VariableType {
+    fn default() -> VariableType {
+        VariableType::EnvVar
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Variable {
+    pub key: String,
+    pub value: Secret,
+    #[serde(default)]
+    pub variable_type: VariableType,
+    #[serde(default)]
+    pub protected: bool,
+    #[serde(default)]
+    pub masked: bool,
+    #[serde(default)]
+    pub raw: bool,
+    pub environment_scope: Option<String>,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct VariableParams {
+    pub key: String,
+    pub value: Secret,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub variable_type: Option<VariableType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub protected: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub masked: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub raw: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub environment_scope: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl VariableParams {
+    pub fn builder<T, U>(key: T, value: U) -> VariableParamsBuilder
+        where T: Into<String>,
+              U: Into<Secret>,
+    {
+        VariableParamsBuilder::new(key, value)
+    }
+}
+
+pub struct VariableParamsBuilder(VariableParams);
+
+impl VariableParamsBuilder {
+    pub fn new<T, U>(key: T, value: U) -> Self
+        where T: Into<String>,
+              U: Into<Secret>,
+    {
+        VariableParamsBuilder(VariableParams {
+            key: key.into(),
+            value: value.into(),
+            ..Default::default()
+        })
+    }
+
+    pub fn variable_type(&mut self, variable_type: VariableType) -> &mut Self {
+        self.0.variable_type = Some(variable_type);
+        self
+    }
+
+    pub fn protected(&mut self, protected: bool) -> &mut Self {
+        self.0.protected = Some(protected);
+        self
+    }
+
+    pub fn masked(&mut self, masked: bool) -> &mut Self {
+        self.0.masked = Some(masked);
+        self
+    }
+
+    /// Treat the value as raw, i.e. do not expand `$VARIABLE` references.
+    pub fn raw(&mut self, raw: bool) -> &mut Self {
+        self.0.raw = Some(raw);
+        self
+    }
+
+    /// Not supported for instance variables.
+    pub fn environment_scope<T>(&mut self, scope: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.environment_scope = Some(scope.into());
+        self
+    }
+
+    pub fn description<T>(&mut self, description: T) -> &mut Self
+        where T: Into<String>
+    {
+        self.0.description = Some(description.into());
+        self
+    }
+
+    pub fn build(&self) -> VariableParams {
+        self.0.clone()
+    }
+}
+
+impl<'a> Variables<'a> {
+    #[doc(hidden)]
+    pub fn new(gitlab: &'a Gitlab, scope: VariableScope) -> Variables<'a> {
+        Variables {
+            gitlab: gitlab,
+            scope: scope,
+        }
+    }
+
+    fn resource(&self, more: &str) -> String {
+        match self.scope {
+            VariableScope::Project(ref id) => format!(
+                "/projects/{}/variables{}",
+                utf8_percent_encode(id, PATH_SEGMENT_ENCODE_SET),
+                more
+            ),
+            VariableScope::Group(ref id) => format!(
+                "/groups/{}/variables{}",
+                utf8_percent_encode(id, PATH_SEGMENT_ENCODE_SET),
+                more
+            ),
+            VariableScope::Instance => format!("/admin/ci/variables{}", more),
+        }
+    }
+
+    /// Resource for a single key. Several variables may share a key with
+    /// different environment scopes, in which case GitLab needs
+    /// `filter[environment_scope]` to pick one.
+    fn key_resource(&self, key: &str, environment_scope: Option<&str>) -> String {
+        let encoded_key = utf8_percent_encode(key, PATH_SEGMENT_ENCODE_SET).to_string();
+        let mut uri = vec![self.resource(&format!("/{}", encoded_key))];
+        match (&self.scope, environment_scope) {
+            (&VariableScope::Instance, _) | (_, None) => {}
+            (_, Some(environment_scope)) => {
+                uri.push(form_urlencoded::Serializer::new(String::new())
+                    .append_pair("filter[environment_scope]", environment_scope)
+                    .finish());
+            }
+        }
+        uri.join("?")
+    }
+
+    pub fn list(&self) -> Result<Vec<Variable>> {
+        self.gitlab.get_all::<Variable>(&self.resource(""))
+    }
+
+    pub fn variable<T>(&self, key: T, environment_scope: Option<&str>) -> Result<Variable>
+        where T: Into<String>
+    {
+        self.gitlab.get::<Variable>(&self.key_resource(&key.into(), environment_scope))
+    }
+
+    pub fn create(&self, params: &VariableParams) -> Result<Variable> {
+        let json = serde_json::to_string(&params)?;
+        self.gitlab.post::<Variable>(&self.resource(""), json.into_bytes())
+    }
+
+    /// Update the variable named by `params.key`. Pass the variable's current
+    /// environment scope as `environment_scope` when the key is not unique.
+    pub fn update(&self, params: &VariableParams, environment_scope: Option<&str>) -> Result<Variable> {
+        let json = serde_json::to_string(&params)?;
+        self.gitlab.put::<Variable>(&self.key_resource(&params.key, environment_scope), json.into_bytes())
+    }
+
+    pub fn delete<T>(&self, key: T, environment_scope: Option<&str>) -> Result<()>
+        where T: Into<String>
+    {
+        self.gitlab.delete(&self.key_resource(&key.into(), environment_scope))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Variable, VariableParams, VariableScope, VariableType, Variables};
+    use {Credentials, Gitlab};
+    use reqwest::Client;
+    use serde_json;
+
+    #[test]
+    fn key_resource_per_scope() {
+        let gitlab = Gitlab::new("https://gitlab.example.com", Client::new().unwrap(), Credentials::Anonymous);
+
+        let project = Variables::new(&gitlab, VariableScope::Project(String::from("group/app")));
+        assert_eq!(project.key_resource("DEPLOY_KEY", None), "/projects/group%2Fapp/variables/DEPLOY_KEY");
+        assert_eq!(project.key_resource("DEPLOY_KEY", Some("review/*")),
+                   "/projects/group%2Fapp/variables/DEPLOY_KEY?filter%5Benvironment_scope%5D=review%2F*");
+
+        let group = Variables::new(&gitlab, VariableScope::Group(String::from("7")));
+        assert_eq!(group.key_resource("TOKEN", Some("production")),
+                   "/groups/7/variables/TOKEN?filter%5Benvironment_scope%5D=production");
+
+        let instance = Variables::new(&gitlab, VariableScope::Instance);
+        assert_eq!(instance.key_resource("TOKEN", None), "/admin/ci/variables/TOKEN");
+        assert_eq!(instance.key_resource("TOKEN", Some("production")), "/admin/ci/variables/TOKEN");
+    }
+
+    #[test]
+    fn values_are_redacted() {
+        let variable: Variable = serde_json::from_str(r#"{
+            "key": "DEPLOY_KEY",
+            "value": "hunter2",
+            "variable_type": "file",
+            "protected": true,
+            "masked": true,
+            "environment_scope": "*"
+        }"#).unwrap();
+        assert_eq!(variable.value.expose(), "hunter2");
+        assert_eq!(variable.variable_type, VariableType::File);
+        assert!(!format!("{:?}", variable).contains("hunter2"));
+
+        let params = VariableParams::builder("DEPLOY_KEY", "hunter2").masked(true).build();
+        assert!(!format!("{:?}", params).contains("hunter2"));
+        assert_eq!(serde_json::to_string(&params).unwrap(),
+                   r#"{"key":"DEPLOY_KEY","value":"hunter2","masked":true}"#);
+    }
+}
@@ -0,0 +1,745 @@
+//! Payloads for incoming [webhook events](https://docs.gitlab.com/ee/user/project/integrations/webhook_events.html)
+//! and a receiver that checks and parses them.
+//!
+//! Users reuse `projects::User`. Projects are described differently in
+//! payloads than by the API (the namespace is a plain string, for one), so
+//! they have their own `EventProject` type.
+
+use self::super::{Error, ErrorKind, Result};
+use projects::User;
+use secret::Secret;
+
+use serde_json::{self, Value};
+
+/// Header carrying the event name, e.g. `Push Hook`.
+pub const EVENT_HEADER: &'static str = "X-Gitlab-Event";
+
+/// Header carrying the secret token configured on the hook.
+pub const TOKEN_HEADER: &'static str = "X-Gitlab-Token";
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct EventProject {
+    pub id: u64,
+    pub name: String,
+    pub description: Option<String>,
+    pub web_url: String,
+    pub avatar_url: Option<String>,
+    pub git_ssh_url: Option<String>,
+    pub git_http_url: Option<String>,
+    pub namespace: String,
+    pub visibility_level: Option<u8>,
+    pub path_with_namespace: String,
+    pub default_branch: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct EventRepository {
+    pub name: String,
+    pub url: Option<String>,
+    pub description: Option<String>,
+    pub homepage: Option<String>,
+    pub git_http_url: Option<String>,
+    pub git_ssh_url: Option<String>,
+    pub visibility_level: Option<u8>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct EventCommitAuthor {
+    pub name: String,
+    pub email: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct EventCommit {
+    pub id: String,
+    pub message: String,
+    pub title: Option<String>,
+    pub timestamp: Option<String>,
+    pub url: Option<String>,
+    pub author: Option<EventCommitAuthor>,
+    #[serde(default)]
+    pub added: Vec<String>,
+    #[serde(default)]
+    pub modified: Vec<String>,
+    #[serde(default)]
+    pub removed: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct EventLabel {
+    pub id: u64,
+    pub title: String,
+    pub color: Option<String>,
+    pub description: Option<String>,
+    #[serde(rename = "type")]
+    pub label_type: Option<String>,
+}
+
+/// Push and tag push events.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PushEvent {
+    pub object_kind: String,
+    pub event_name: Option<String>,
+    pub before: String,
+    pub after: String,
+    #[serde(rename = "ref")]
+    pub ref_name: String,
+    pub checkout_sha: Option<String>,
+    pub user_id: u64,
+    pub user_name: String,
+    pub user_username: Option<String>,
+    pub user_email: Option<String>,
+    pub user_avatar: Option<String>,
+    pub project_id: u64,
+    pub project: EventProject,
+    #[serde(default)]
+    pub commits: Vec<EventCommit>,
+    pub total_commits_count: u64,
+    pub repository: EventRepository,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct IssueAttributes {
+    pub id: u64,
+    pub iid: u64,
+    pub title: String,
+    pub description: Option<String>,
+    pub state: String,
+    pub action: Option<String>,
+    pub url: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+    pub confidential: Option<bool>,
+    pub project_id: u64,
+    pub author_id: u64,
+}
+
+/// Issue and confidential issue events.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct IssueEvent {
+    pub object_kind: String,
+    pub event_type: Option<String>,
+    pub user: User,
+    pub project: EventProject,
+    pub object_attributes: IssueAttributes,
+    #[serde(default)]
+    pub labels: Vec<EventLabel>,
+    #[serde(default)]
+    pub assignees: Vec<User>,
+    #[serde(default)]
+    pub changes: Value,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MergeRequestAttributes {
+    pub id: u64,
+    pub iid: u64,
+    pub title: String,
+    pub description: Option<String>,
+    pub state: String,
+    pub action: Option<String>,
+    pub source_branch: String,
+    pub target_branch: String,
+    pub source_project_id: u64,
+    pub target_project_id: u64,
+    pub author_id: u64,
+    pub merge_status: Option<String>,
+    pub url: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+    pub last_commit: Option<EventCommit>,
+    pub draft: Option<bool>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MergeRequestEvent {
+    pub object_kind: String,
+    pub event_type: Option<String>,
+    pub user: User,
+    pub project: EventProject,
+    pub object_attributes: MergeRequestAttributes,
+    #[serde(default)]
+    pub labels: Vec<EventLabel>,
+    #[serde(default)]
+    pub assignees: Vec<User>,
+    #[serde(default)]
+    pub reviewers: Vec<User>,
+    #[serde(default)]
+    pub changes: Value,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct NoteAttributes {
+    pub id: u64,
+    pub note: String,
+    pub noteable_type: String,
+    pub noteable_id: Option<u64>,
+    pub author_id: u64,
+    pub project_id: u64,
+    pub created_at: String,
+    pub updated_at: String,
+    pub system: Option<bool>,
+    pub url: Option<String>,
+}
+
+/// Comment events on commits, merge requests, issues and snippets. Only the
+/// field matching `object_attributes.noteable_type` is present.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct NoteEvent {
+    pub object_kind: String,
+    pub event_type: Option<String>,
+    pub user: User,
+    pub project_id: u64,
+    pub project: EventProject,
+    pub object_attributes: NoteAttributes,
+    pub commit: Option<EventCommit>,
+    pub merge_request: Option<MergeRequestAttributes>,
+    pub issue: Option<IssueAttributes>,
+    pub snippet: Option<Value>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct EventWiki {
+    pub web_url: String,
+    pub git_ssh_url: Option<String>,
+    pub git_http_url: Option<String>,
+    pub path_with_namespace: String,
+    pub default_branch: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WikiPageAttributes {
+    pub title: String,
+    pub content: Option<String>,
+    pub format: String,
+    pub message: Option<String>,
+    pub slug: String,
+    pub url: String,
+    pub action: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WikiPageEvent {
+    pub object_kind: String,
+    pub user: User,
+    pub project: EventProject,
+    pub wiki: EventWiki,
+    pub object_attributes: WikiPageAttributes,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct EventVariable {
+    pub key: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PipelineAttributes {
+    pub id: u64,
+    pub iid: Option<u64>,
+    #[serde(rename = "ref")]
+    pub ref_name: String,
+    pub tag: bool,
+    pub sha: String,
+    pub before_sha: Option<String>,
+    pub source: Option<String>,
+    pub status: String,
+    pub detailed_status: Option<String>,
+    #[serde(default)]
+    pub stages: Vec<String>,
+    pub created_at: String,
+    pub finished_at: Option<String>,
+    pub duration: Option<u64>,
+    #[serde(default)]
+    pub variables: Vec<EventVariable>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PipelineBuild {
+    pub id: u64,
+    pub stage: String,
+    pub name: String,
+    pub status: String,
+    pub created_at: String,
+    pub started_at: Option<String>,
+    pub finished_at: Option<String>,
+    pub when: Option<String>,
+    pub manual: bool,
+    pub allow_failure: bool,
+    pub user: Option<User>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PipelineEvent {
+    pub object_kind: String,
+    pub object_attributes: PipelineAttributes,
+    pub user: User,
+    pub project: EventProject,
+    pub commit: Option<EventCommit>,
+    #[serde(default)]
+    pub builds: Vec<PipelineBuild>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct JobCommit {
+    pub id: u64,
+    pub sha: String,
+    pub message: String,
+    pub author_name: String,
+    pub author_email: Option<String>,
+    pub status: String,
+    pub duration: Option<f64>,
+    pub started_at: Option<String>,
+    pub finished_at: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct JobEvent {
+    pub object_kind: String,
+    #[serde(rename = "ref")]
+    pub ref_name: String,
+    pub tag: bool,
+    pub before_sha: Option<String>,
+    pub sha: String,
+    pub build_id: u64,
+    pub build_name: String,
+    pub build_stage: String,
+    pub build_status: String,
+    pub build_created_at: String,
+    pub build_started_at: Option<String>,
+    pub build_finished_at: Option<String>,
+    pub build_duration: Option<f64>,
+    pub build_allow_failure: bool,
+    pub build_failure_reason: Option<String>,
+    pub pipeline_id: u64,
+    pub project_id: u64,
+    pub project_name: String,
+    pub user: User,
+    pub commit: JobCommit,
+    pub repository: EventRepository,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DeploymentEvent {
+    pub object_kind: String,
+    pub status: String,
+    pub status_changed_at: Option<String>,
+    pub deployment_id: u64,
+    pub deployable_id: Option<u64>,
+    pub deployable_url: Option<String>,
+    pub environment: String,
+    pub project: EventProject,
+    pub short_sha: String,
+    pub user: User,
+    pub user_url: Option<String>,
+    pub commit_url: Option<String>,
+    pub commit_title: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ReleaseEvent {
+    pub object_kind: String,
+    pub id: u64,
+    pub action: String,
+    pub name: String,
+    pub tag: String,
+    pub description: Option<String>,
+    pub created_at: String,
+    pub released_at: Option<String>,
+    pub url: String,
+    pub project: EventProject,
+    pub commit: EventCommit,
+    #[serde(default)]
+    pub assets: Value,
+}
+
+/// Project lifecycle system events: `project_create`, `project_destroy`,
+/// `project_rename`, `project_transfer` and `project_update`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ProjectSystemEvent {
+    pub created_at: String,
+    pub updated_at: String,
+    pub name: String,
+    pub path: String,
+    pub path_with_namespace: String,
+    pub project_id: u64,
+    pub owner_name: Option<String>,
+    pub owner_email: Option<String>,
+    pub project_visibility: Option<String>,
+    pub old_path_with_namespace: Option<String>,
+}
+
+/// Project membership system events: `user_add_to_team`,
+/// `user_remove_from_team` and `user_update_for_team`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TeamMemberSystemEvent {
+    pub created_at: String,
+    pub updated_at: String,
+    pub access_level: String,
+    pub project_id: u64,
+    pub project_name: String,
+    pub project_path: String,
+    pub project_path_with_namespace: String,
+    pub project_visibility: Option<String>,
+    pub user_id: u64,
+    pub user_name: String,
+    pub user_username: String,
+    pub user_email: Option<String>,
+}
+
+/// User system events: `user_create`, `user_destroy`, `user_rename` and
+/// `user_failed_login`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct UserSystemEvent {
+    pub created_at: String,
+    pub updated_at: String,
+    pub user_id: u64,
+    pub name: String,
+    pub username: String,
+    pub email: Option<String>,
+    pub old_username: Option<String>,
+    pub state: Option<String>,
+}
+
+/// SSH key system events: `key_create` and `key_destroy`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct KeySystemEvent {
+    pub created_at: String,
+    pub updated_at: String,
+    pub id: u64,
+    pub username: String,
+    pub key: String,
+}
+
+/// Group system events: `group_create`, `group_destroy` and `group_rename`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct GroupSystemEvent {
+    pub created_at: String,
+    pub updated_at: String,
+    pub group_id: u64,
+    pub name: String,
+    pub path: String,
+    pub full_path: Option<String>,
+    pub old_path: Option<String>,
+    pub old_full_path: Option<String>,
+}
+
+/// Group membership system events: `user_add_to_group`,
+/// `user_remove_from_group` and `user_update_for_group`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct GroupMemberSystemEvent {
+    pub created_at: String,
+    pub updated_at: String,
+    pub group_access: String,
+    pub group_id: u64,
+    pub group_name: String,
+    pub group_path: String,
+    pub user_id: u64,
+    pub user_name: String,
+    pub user_username: String,
+    pub user_email: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RefChange {
+    pub before: String,
+    pub after: String,
+    #[serde(rename = "ref")]
+    pub ref_name: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RepositoryUpdateEvent {
+    pub user_id: u64,
+    pub user_name: String,
+    pub user_email: Option<String>,
+    pub user_avatar: Option<String>,
+    pub project_id: u64,
+    pub project: EventProject,
+    #[serde(default)]
+    pub changes: Vec<RefChange>,
+    #[serde(default)]
+    pub refs: Vec<String>,
+}
+
+/// A [system hook](https://docs.gitlab.com/ee/administration/system_hooks.html)
+/// delivery, identified by its `event_name` (or `object_kind` for merge
+/// requests).
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum SystemHookEvent {
+    ProjectCreate(ProjectSystemEvent),
+    ProjectDestroy(ProjectSystemEvent),
+    ProjectRename(ProjectSystemEvent),
+    ProjectTransfer(ProjectSystemEvent),
+    ProjectUpdate(ProjectSystemEvent),
+    UserAddToTeam(TeamMemberSystemEvent),
+    UserRemoveFromTeam(TeamMemberSystemEvent),
+    UserUpdateForTeam(TeamMemberSystemEvent),
+    UserCreate(UserSystemEvent),
+    UserDestroy(UserSystemEvent),
+    UserRename(UserSystemEvent),
+    UserFailedLogin(UserSystemEvent),
+    KeyCreate(KeySystemEvent),
+    KeyDestroy(KeySystemEvent),
+    GroupCreate(GroupSystemEvent),
+    GroupDestroy(GroupSystemEvent),
+    GroupRename(GroupSystemEvent),
+    UserAddToGroup(GroupMemberSystemEvent),
+    UserRemoveFromGroup(GroupMemberSystemEvent),
+    UserUpdateForGroup(GroupMemberSystemEvent),
+    Push(PushEvent),
+    TagPush(PushEvent),
+    MergeRequest(MergeRequestEvent),
+    RepositoryUpdate(RepositoryUpdateEvent),
+}
+
+impl SystemHookEvent {
+    pub fn parse(body: &[u8]) -> Result<SystemHookEvent> {
+        let value: Value = serde_json::from_slice(body)?;
+        let name = value.get("event_name")
+            .or_else(|| value.get("object_kind"))
+            .and_then(|name| name.as_str())
+            .unwrap_or("")
+            .to_owned();
+
+        let event = match &*name {
+            "project_create" => SystemHookEvent::ProjectCreate(serde_json::from_value(value)?),
+            "project_destroy" => SystemHookEvent::ProjectDestroy(serde_json::from_value(value)?),
+            "project_rename" => SystemHookEvent::ProjectRename(serde_json::from_value(value)?),
+            "project_transfer" => SystemHookEvent::ProjectTransfer(serde_json::from_value(value)?),
+            "project_update" => SystemHookEvent::ProjectUpdate(serde_json::from_value(value)?),
+            "user_add_to_team" => SystemHookEvent::UserAddToTeam(serde_json::from_value(value)?),
+            "user_remove_from_team" => SystemHookEvent::UserRemoveFromTeam(serde_json::from_value(value)?),
+            "user_update_for_team" => SystemHookEvent::UserUpdateForTeam(serde_json::from_value(value)?),
+            "user_create" => SystemHookEvent::UserCreate(serde_json::from_value(value)?),
+            "user_destroy" => SystemHookEvent::UserDestroy(serde_json::from_value(value)?),
+            "user_rename" => SystemHookEvent::UserRename(serde_json::from_value(value)?),
+            "user_failed_login" => SystemHookEvent::UserFailedLogin(serde_json::from_value(value)?),
+            "key_create" => SystemHookEvent::KeyCreate(serde_json::from_value(value)?),
+            "key_destroy" => SystemHookEvent::KeyDestroy(serde_json::from_value(value)?),
+            "group_create" => SystemHookEvent::GroupCreate(serde_json::from_value(value)?),
+            "group_destroy" => SystemHookEvent::GroupDestroy(serde_json::from_value(value)?),
+            "group_rename" => SystemHookEvent::GroupRename(serde_json::from_value(value)?),
+            "user_add_to_group" => SystemHookEvent::UserAddToGroup(serde_json::from_value(value)?),
+            "user_remove_from_group" => SystemHookEvent::UserRemoveFromGroup(serde_json::from_value(value)?),
+            "user_update_for_group" => SystemHookEvent::UserUpdateForGroup(serde_json::from_value(value)?),
+            "push" => SystemHookEvent::Push(serde_json::from_value(value)?),
+            "tag_push" => SystemHookEvent::TagPush(serde_json::from_value(value)?),
+            "merge_request" => SystemHookEvent::MergeRequest(serde_json::from_value(value)?),
+            "repository_update" => SystemHookEvent::RepositoryUpdate(serde_json::from_value(value)?),
+            _ => return Err(ErrorKind::UnknownWebhookEvent(name).into()),
+        };
+        Ok(event)
+    }
+
+    /// The `event_name` of the delivery.
+    pub fn name(&self) -> &'static str {
+        match *self {
+            SystemHookEvent::ProjectCreate(_) => "project_create",
+            SystemHookEvent::ProjectDestroy(_) => "project_destroy",
+            SystemHookEvent::ProjectRename(_) => "project_rename",
+            SystemHookEvent::ProjectTransfer(_) => "project_transfer",
+            SystemHookEvent::ProjectUpdate(_) => "project_update",
+            SystemHookEvent::UserAddToTeam(_) => "user_add_to_team",
+            SystemHookEvent::UserRemoveFromTeam(_) => "user_remove_from_team",
+            SystemHookEvent::UserUpdateForTeam(_) => "user_update_for_team",
+            SystemHookEvent::UserCreate(_) => "user_create",
+            SystemHookEvent::UserDestroy(_) => "user_destroy",
+            SystemHookEvent::UserRename(_) => "user_rename",
+            SystemHookEvent::UserFailedLogin(_) => "user_failed_login",
+            SystemHookEvent::KeyCreate(_) => "key_create",
+            SystemHookEvent::KeyDestroy(_) => "key_destroy",
+            SystemHookEvent::GroupCreate(_) => "group_create",
+            SystemHookEvent::GroupDestroy(_) => "group_destroy",
+            SystemHookEvent::GroupRename(_) => "group_rename",
+            SystemHookEvent::UserAddToGroup(_) => "user_add_to_group",
+            SystemHookEvent::UserRemoveFromGroup(_) => "user_remove_from_group",
+            SystemHookEvent::UserUpdateForGroup(_) => "user_update_for_group",
+            SystemHookEvent::Push(_) => "push",
+            SystemHookEvent::TagPush(_) => "tag_push",
+            SystemHookEvent::MergeRequest(_) => "merge_request",
+            SystemHookEvent::RepositoryUpdate(_) => "repository_update",
+        }
+    }
+}
+
+/// A parsed webhook delivery.
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum WebhookEvent {
+    Push(PushEvent),
+    TagPush(PushEvent),
+    Issue(IssueEvent),
+    Note(NoteEvent),
+    MergeRequest(MergeRequestEvent),
+    WikiPage(WikiPageEvent),
+    Pipeline(PipelineEvent),
+    Job(JobEvent),
+    Deployment(DeploymentEvent),
+    Release(ReleaseEvent),
+    System(SystemHookEvent),
+}
+
+impl WebhookEvent {
+    /// Parse a delivery according to its `X-Gitlab-Event` header.
+    pub fn parse(event: &str, body: &[u8]) -> Result<WebhookEvent> {
+        let event = match event {
+            "Push Hook" => WebhookEvent::Push(serde_json::from_slice(body)?),
+            "Tag Push Hook" => WebhookEvent::TagPush(serde_json::from_slice(body)?),
+            "Issue Hook" | "Confidential Issue Hook" => WebhookEvent::Issue(serde_json::from_slice(body)?),
+            "Note Hook" | "Confidential Note Hook" => WebhookEvent::Note(serde_json::from_slice(body)?),
+            "Merge Request Hook" => WebhookEvent::MergeRequest(serde_json::from_slice(body)?),
+            "Wiki Page Hook" => WebhookEvent::WikiPage(serde_json::from_slice(body)?),
+            "Pipeline Hook" => WebhookEvent::Pipeline(serde_json::from_slice(body)?),
+            "Job Hook" => WebhookEvent::Job(serde_json::from_slice(body)?),
+            "Deployment Hook" => WebhookEvent::Deployment(serde_json::from_slice(body)?),
+            "Release Hook" => WebhookEvent::Release(serde_json::from_slice(body)?),
+            "System Hook" => WebhookEvent::System(SystemHookEvent::parse(body)?),
+            other => return Err(ErrorKind::UnknownWebhookEvent(other.to_owned()).into()),
+        };
+        Ok(event)
+    }
+
+    /// Short snake_case name of the event, e.g. `push` or `merge_request`.
+    /// System hook deliveries use their `event_name`, e.g. `project_create`.
+    pub fn kind(&self) -> &'static str {
+        match *self {
+            WebhookEvent::Push(_) => "push",
+            WebhookEvent::TagPush(_) => "tag_push",
+            WebhookEvent::Issue(_) => "issue",
+            WebhookEvent::Note(_) => "note",
+            WebhookEvent::MergeRequest(_) => "merge_request",
+            WebhookEvent::WikiPage(_) => "wiki_page",
+            WebhookEvent::Pipeline(_) => "pipeline",
+            WebhookEvent::Job(_) => "job",
+            WebhookEvent::Deployment(_) => "deployment",
+            WebhookEvent::Release(_) => "release",
+            WebhookEvent::System(ref event) => event.name(),
+        }
+    }
+}
+
+/// Compare a received `X-Gitlab-Token` against the configured secret in
+/// constant time.
+pub fn verify_token(expected: &Secret, received: Option<&str>) -> bool {
+    let received = match received {
+        Some(received) => received.as_bytes(),
+        None => return false,
+    };
+    let expected = expected.expose().as_bytes();
+    if expected.len() != received.len() {
+        return false;
+    }
+    expected.iter().zip(received).fold(0u8, |acc, (a, b)| acc | (a ^ b)) == 0
+}
+
+/// Checks and parses incoming deliveries, independent of the HTTP server
+/// they arrive through.
+#[derive(Debug, Clone, Default)]
+pub struct WebhookReceiver {
+    token: Option<Secret>,
+}
+
+impl WebhookReceiver {
+    /// A receiver that accepts deliveries without checking a token.
+    pub fn new() -> WebhookReceiver {
+        WebhookReceiver { token: None }
+    }
+
+    /// A receiver that rejects deliveries whose `X-Gitlab-Token` does not
+    /// match `token`.
+    pub fn with_token<T>(token: T) -> WebhookReceiver
+        where T: Into<Secret>
+    {
+        WebhookReceiver { token: Some(token.into()) }
+    }
+
+    /// Check the token and parse the body, given the values of the
+    /// `X-Gitlab-Event` and `X-Gitlab-Token` headers.
+    pub fn receive(&self, event: Option<&str>, token: Option<&str>, body: &[u8]) -> Result<WebhookEvent> {
+        if let Some(ref expected) = self.token {
+            if !verify_token(expected, token) {
+                return Err(ErrorKind::InvalidWebhookToken.into());
+            }
+        }
+        match event {
+            Some(event) => WebhookEvent::parse(event, body),
+            None => Err(ErrorKind::UnknownWebhookEvent(String::new()).into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SystemHookEvent, WebhookEvent, WebhookReceiver, verify_token};
+    use secret::Secret;
+
+    const PUSH: &'static str = r#"{
+        "object_kind": "push",
+        "event_name": "push",
+        "before": "95790bf891e76fee5e1747ab589903a6a1f80f22",
+        "after": "da1560886d4f094c3e6c9ef40349f7d38b5d27d7",
+        "ref": "refs/heads/master",
+        "checkout_sha": "da1560886d4f094c3e6c9ef40349f7d38b5d27d7",
+        "user_id": 4,
+        "user_name": "John Smith",
+        "user_username": "jsmith",
+        "project_id": 15,
+        "project": {
+            "id": 15,
+            "name": "Diaspora",
+            "description": "",
+            "web_url": "http://example.com/mike/diaspora",
+            "avatar_url": null,
+            "namespace": "Mike",
+            "visibility_level": 0,
+            "path_with_namespace": "mike/diaspora",
+            "default_branch": "master"
+        },
+        "commits": [],
+        "total_commits_count": 0,
+        "repository": { "name": "Diaspora" }
+    }"#;
+
+    #[test]
+    fn push_event_is_parsed() {
+        match WebhookEvent::parse("Push Hook", PUSH.as_bytes()).unwrap() {
+            WebhookEvent::Push(push) => {
+                assert_eq!(push.ref_name, "refs/heads/master");
+                assert_eq!(push.project.path_with_namespace, "mike/diaspora");
+            }
+            other => panic!("unexpected event {:?}", other),
+        }
+        assert!(WebhookEvent::parse("Unknown Hook", PUSH.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn token_must_match() {
+        let secret = Secret::new("s3cret");
+        assert!(verify_token(&secret, Some("s3cret")));
+        assert!(!verify_token(&secret, Some("s3cre")));
+        assert!(!verify_token(&secret, None));
+
+        let receiver = WebhookReceiver::with_token("s3cret");
+        assert!(receiver.receive(Some("Push Hook"), Some("wrong"), PUSH.as_bytes()).is_err());
+        assert!(receiver.receive(Some("Push Hook"), Some("s3cret"), PUSH.as_bytes()).is_ok());
+    }
+
+    #[test]
+    fn system_events_are_dispatched_on_event_name() {
+        let body = r#"{
+            "created_at": "2012-07-21T07:30:54Z",
+            "updated_at": "2012-07-21T07:38:22Z",
+            "event_name": "user_create",
+            "email": "js@gitlabhq.com",
+            "name": "John Smith",
+            "username": "js",
+            "user_id": 41
+        }"#;
+        match WebhookEvent::parse("System Hook", body.as_bytes()).unwrap() {
+            WebhookEvent::System(SystemHookEvent::UserCreate(user)) => assert_eq!(user.username, "js"),
+            other => panic!("unexpected event {:?}", other),
+        }
+    }
+}